@@ -1,7 +1,7 @@
 use crate::models::{Brewery, MetaResponse};
 use gloo_net::http::Request;
 use leptos::prelude::*;
-use leptos_struct_table::{ColumnSort, PaginatedTableDataProvider};
+use leptos_struct_table::{AbortToken, ColumnSort, PaginatedTableDataProvider};
 use std::collections::VecDeque;
 
 pub struct BreweryDataProvider {
@@ -74,14 +74,30 @@ impl PaginatedTableDataProvider<Brewery, usize> for BreweryDataProvider {
     const PAGE_ROW_COUNT: usize = 200;
 
     async fn get_page(&self, page_index: usize) -> Result<Vec<Brewery>, String> {
+        self.get_page_with_context(page_index, AbortToken::new()).await
+    }
+
+    async fn get_page_with_context(
+        &self,
+        page_index: usize,
+        abort_token: AbortToken,
+    ) -> Result<Vec<Brewery>, String> {
         if page_index >= 10000 / Self::PAGE_ROW_COUNT {
             return Ok(vec![]);
         }
 
         let (url, query) = self.get_url(page_index);
 
+        // cancel the request if the table doesn't need the page anymore
+        let abort_controller = web_sys::AbortController::new().ok();
+        let abort_signal = abort_controller.as_ref().map(|c| c.signal());
+        if let Some(abort_controller) = abort_controller {
+            abort_token.on_abort(move || abort_controller.abort());
+        }
+
         let resp: Vec<Brewery> = Request::get(url)
             .query(query)
+            .abort_signal(abort_signal.as_ref())
             .send()
             .await
             .map_err(|e| e.to_string())?
@@ -3,7 +3,7 @@ use leptos::prelude::*;
 use leptos_struct_table::*;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
-use sqlx::{QueryBuilder, Row};
+use sqlx::Row;
 use std::collections::VecDeque;
 use std::ops::Range;
 
@@ -36,28 +36,32 @@ pub async fn list_customers(query: CustomerQuery) -> Result<Vec<Customer>, Serve
 
     let CustomerQuery { sort, range, name } = query;
 
-    let mut query = QueryBuilder::new("SELECT customer_id, first_name, last_name, company, city, country, phone, email, website FROM customers ");
-    if !name.is_empty() {
-        query.push("WHERE first_name LIKE concat('%', ");
-        query.push_bind(&name);
-        query.push(", '%') OR last_name LIKE concat('%', ");
-        query.push_bind(&name);
-        query.push(", '%') OR company LIKE concat('%', ");
-        query.push_bind(&name);
-        query.push(", '%') ");
+    let filter = FilterModel {
+        search: name,
+        ..Default::default()
+    };
+
+    let query = SqlBuilder::<Customer, usize>::new(SqlDialect::Sqlite)
+        .push_sql("SELECT customer_id, first_name, last_name, company, city, country, phone, email, website FROM customers")
+        // search only by first name, last name and company with a plain `LIKE`
+        .search_columns(&[1, 2, 3])
+        .case_sensitive(true)
+        .filter(&filter)
+        .order_by(&sort)
+        .range(range)
+        .build();
+
+    let mut sql_query = sqlx::query_as::<_, Customer>(&query.sql);
+    for bind in query.binds {
+        sql_query = match bind {
+            FilterValue::Bool(value) => sql_query.bind(value),
+            FilterValue::Int(value) => sql_query.bind(value),
+            FilterValue::Float(value) => sql_query.bind(value),
+            FilterValue::Text(value) => sql_query.bind(value),
+        };
     }
 
-    if let Some(order) = Customer::sorting_to_sql(&sort) {
-        query.push(order);
-    }
-
-    query.push(" LIMIT ");
-    query.push_bind(range.len() as i64);
-    query.push(" OFFSET ");
-    query.push_bind(range.start as i64);
-
-    query
-        .build_query_as::<Customer>()
+    sql_query
         .fetch_all(get_db())
         .await
         .map_err(|e| ServerFnError::new(format!("{e:?}")))
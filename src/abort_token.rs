@@ -0,0 +1,107 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Passed to [`TableDataProvider::get_rows_with_context`] to signal that the result of a request
+/// is not needed anymore.
+///
+/// This happens when the sorting changes, when [`ReloadController::reload`] is called or when the
+/// requested rows are scrolled out of view before they finished loading. Data providers can use this
+/// to abort expensive requests. The result of an aborted request is ignored by [`TableContent`] in any case.
+#[derive(Clone, Default)]
+pub struct AbortToken(Rc<AbortTokenInner>);
+
+#[derive(Default)]
+struct AbortTokenInner {
+    aborted: Cell<bool>,
+    on_abort: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl AbortToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the request has been aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.0.aborted.get()
+    }
+
+    /// Registers a callback that is called once the request is aborted. If it has been aborted
+    /// already the callback is called immediately.
+    ///
+    /// This can be used for example to call `AbortController::abort()` of a running `fetch`.
+    pub fn on_abort(&self, callback: impl FnOnce() + 'static) {
+        if self.is_aborted() {
+            callback();
+        } else {
+            self.0.on_abort.borrow_mut().push(Box::new(callback));
+        }
+    }
+
+    /// Aborts the request. Calling this more than once has no effect.
+    pub fn abort(&self) {
+        if self.0.aborted.replace(true) {
+            return;
+        }
+
+        let callbacks = self.0.on_abort.take();
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    /// Returns `true` if both tokens belong to the same request.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abort_calls_callbacks_once() {
+        let token = AbortToken::new();
+        let calls = Rc::new(Cell::new(0));
+
+        token.on_abort({
+            let calls = Rc::clone(&calls);
+            move || calls.set(calls.get() + 1)
+        });
+        assert!(!token.is_aborted());
+        assert_eq!(calls.get(), 0);
+
+        token.clone().abort();
+        token.abort();
+
+        assert!(token.is_aborted());
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn on_abort_after_abort_is_called_immediately() {
+        let token = AbortToken::new();
+        token.abort();
+
+        let called = Rc::new(Cell::new(false));
+        token.on_abort({
+            let called = Rc::clone(&called);
+            move || called.set(true)
+        });
+
+        assert!(called.get());
+    }
+
+    #[test]
+    fn clones_share_the_state() {
+        let token = AbortToken::new();
+        let clone = token.clone();
+
+        assert!(token.ptr_eq(&clone));
+        assert!(!token.ptr_eq(&AbortToken::new()));
+
+        clone.abort();
+        assert!(token.is_aborted());
+    }
+}
@@ -0,0 +1,224 @@
+use leptos::prelude::*;
+use std::ops::RangeInclusive;
+
+/// The position of a cell given by the index of its row and its column.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CellIndex<Column> {
+    /// The index of the row. Starts at 0 for the first body row.
+    pub row: usize,
+    pub column: Column,
+}
+
+/// A rectangular range of cells spanned by the `anchor` cell where the selection started and the
+/// `focus` cell where it ends. The columns in between are determined by the order of the
+/// visible columns.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CellRange<Column> {
+    pub anchor: CellIndex<Column>,
+    pub focus: CellIndex<Column>,
+}
+
+impl<Column: Copy + PartialEq> CellRange<Column> {
+    /// A range that only contains one cell.
+    pub fn single(cell: CellIndex<Column>) -> Self {
+        Self {
+            anchor: cell,
+            focus: cell,
+        }
+    }
+
+    /// The indices of the rows of this range.
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.row.min(self.focus.row)..=self.anchor.row.max(self.focus.row)
+    }
+
+    /// The columns of this range in the order of `columns` which are the visible columns of the table.
+    /// Returns an empty slice if the anchor or the focus column isn't visible.
+    pub fn columns<'a>(&self, columns: &'a [Column]) -> &'a [Column] {
+        let position = |column: Column| columns.iter().position(|c| *c == column);
+
+        match (position(self.anchor.column), position(self.focus.column)) {
+            (Some(anchor), Some(focus)) => &columns[anchor.min(focus)..=anchor.max(focus)],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the cell is inside of this range given the visible `columns`.
+    pub fn contains(&self, cell: CellIndex<Column>, columns: &[Column]) -> bool {
+        self.rows().contains(&cell.row) && self.columns(columns).contains(&cell.column)
+    }
+}
+
+/// A spreadsheet-like selection of one or more rectangular cell ranges.
+/// Pass an `RwSignal` of this to the `cell_selection` prop of [`TableContent`] to enable it.
+///
+/// - Clicking a cell selects only this cell.
+/// - Shift-clicking extends the last range to the clicked cell.
+/// - Ctrl-clicking (or Cmd-clicking) adds a new range.
+///
+/// The same applies to the keyboard navigation with the arrow keys.
+/// The selected cells get the classes returned by [`TableClassesProvider::selected_cell`].
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// let mut selection = CellSelection::default();
+/// selection.select(CellIndex { row: 1, column: 0 });
+/// selection.extend(CellIndex { row: 3, column: 2 });
+///
+/// let columns = [0, 1, 2, 3];
+/// assert!(selection.contains(CellIndex { row: 2, column: 1 }, &columns));
+/// assert!(!selection.contains(CellIndex { row: 2, column: 3 }, &columns));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CellSelection<Column> {
+    /// The selected ranges. The last one is the one that is extended.
+    pub ranges: Vec<CellRange<Column>>,
+}
+
+impl<Column> Default for CellSelection<Column> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<Column: Copy + PartialEq> CellSelection<Column> {
+    /// Selects only the given cell.
+    pub fn select(&mut self, cell: CellIndex<Column>) {
+        self.ranges = vec![CellRange::single(cell)];
+    }
+
+    /// Adds a new range that contains only the given cell.
+    pub fn add(&mut self, cell: CellIndex<Column>) {
+        self.ranges.push(CellRange::single(cell));
+    }
+
+    /// Moves the focus of the last range to the given cell. Selects the cell if there is no range.
+    pub fn extend(&mut self, cell: CellIndex<Column>) {
+        match self.ranges.last_mut() {
+            Some(range) => range.focus = cell,
+            None => self.select(cell),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The range that is currently extended.
+    pub fn last_range(&self) -> Option<&CellRange<Column>> {
+        self.ranges.last()
+    }
+
+    /// Returns `true` if the cell is in one of the ranges given the visible `columns`.
+    pub fn contains(&self, cell: CellIndex<Column>, columns: &[Column]) -> bool {
+        self.ranges
+            .iter()
+            .any(|range| range.contains(cell, columns))
+    }
+}
+
+/// Provided as context by [`TableContent`] to the cells of every rendered row if the
+/// `cell_selection` prop is given. [`DefaultTableCellRenderer`] uses it to add the classes of
+/// [`TableClassesProvider::selected_cell`] to the selected cells. Use it in your custom cell
+/// renderer to do the same.
+#[derive(Copy, Clone)]
+pub struct CellSelectionContext<Column: Send + Sync + 'static> {
+    pub(crate) row: usize,
+    pub(crate) is_selected: Callback<CellIndex<Column>, bool>,
+    pub(crate) selected_class: Signal<String>,
+}
+
+impl<Column: Send + Sync + 'static> CellSelectionContext<Column> {
+    /// Returns `true` if the cell of the `column` in this row is selected. Call this in a reactive
+    /// closure to track the selection.
+    pub fn is_selected(&self, column: Column) -> bool {
+        self.is_selected.run(CellIndex {
+            row: self.row,
+            column,
+        })
+    }
+
+    /// Adds the classes of [`TableClassesProvider::selected_cell`] to the `class` of the cell
+    /// of the `column` in this row if it is selected. Call this in a reactive closure.
+    pub fn cell_class(&self, class: &str, column: Column) -> String {
+        if !self.is_selected(column) {
+            return class.to_string();
+        }
+
+        let selected_class = self.selected_class.read();
+        match (class.is_empty(), selected_class.is_empty()) {
+            (_, true) => class.to_string(),
+            (true, false) => selected_class.clone(),
+            (false, false) => format!("{class} {selected_class}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(row: usize, column: usize) -> CellIndex<usize> {
+        CellIndex { row, column }
+    }
+
+    #[test]
+    fn context_adds_selected_class() {
+        let owner = Owner::new();
+        owner.set();
+
+        let selection = RwSignal::new(CellSelection::default());
+        selection.write().select(cell(2, 1));
+
+        let context = CellSelectionContext {
+            row: 2,
+            is_selected: Callback::new(move |index| {
+                selection.with(|selection| selection.contains(index, &[0, 1, 2]))
+            }),
+            selected_class: Signal::stored("active".to_string()),
+        };
+
+        assert_eq!(context.cell_class("cell", 1), "cell active");
+        assert_eq!(context.cell_class("", 1), "active");
+        assert_eq!(context.cell_class("cell", 0), "cell");
+
+        selection.write().clear();
+        assert_eq!(context.cell_class("cell", 1), "cell");
+    }
+
+    #[test]
+    fn range_follows_column_order() {
+        let range = CellRange {
+            anchor: cell(5, 3),
+            focus: cell(2, 1),
+        };
+
+        assert_eq!(range.rows(), 2..=5);
+        assert_eq!(range.columns(&[0, 1, 2, 3]), &[1, 2, 3]);
+        assert_eq!(range.columns(&[3, 0, 1]), &[3, 0, 1]);
+        assert_eq!(range.columns(&[0, 1]), &[] as &[usize]);
+    }
+
+    #[test]
+    fn add_and_extend_ranges() {
+        let columns = [0, 1, 2];
+        let mut selection = CellSelection::default();
+
+        selection.extend(cell(0, 0));
+        selection.add(cell(4, 2));
+        selection.extend(cell(5, 1));
+
+        assert_eq!(selection.ranges.len(), 2);
+        assert!(selection.contains(cell(0, 0), &columns));
+        assert!(!selection.contains(cell(0, 1), &columns));
+        assert!(selection.contains(cell(5, 2), &columns));
+        assert!(!selection.contains(cell(3, 2), &columns));
+
+        selection.select(cell(1, 1));
+        assert_eq!(selection.ranges, vec![CellRange::single(cell(1, 1))]);
+    }
+}
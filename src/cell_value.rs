@@ -1,9 +1,52 @@
+use crate::number_format::format_number;
+use crate::{Notation, NumberLocale, SignDisplay};
 use leptos::prelude::*;
 
+/// Formatting options for numbers. Unless the `locale` is given, the [`NumberLocale`] of the table is
+/// used (see [`CellValue::set_default_locale`]).
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos::prelude::*;
+/// #[derive(TableRow, Clone)]
+/// #[table]
+/// struct Sales {
+///     #[table(format(precision = 2usize, thousands_separator = true, locale = "de"))]
+///     revenue: f64,
+///     #[table(format(percent = true, precision = 1usize, sign_display = "except_zero"))]
+///     growth: f64,
+///     #[table(format(notation = "compact"))]
+///     visitors: u64,
+///     #[table(format(currency = "EUR"))]
+///     price: f64,
+///     #[table(format(unit = "kg", precision = 1usize))]
+///     weight: f32,
+/// }
+/// ```
 #[derive(Default, Clone, Copy)]
 pub struct NumberRenderOptions {
     /// Specifies the number of digits to display after the decimal point
     pub precision: Option<usize>,
+    /// Rounds to this number of significant digits instead. Takes precedence over `precision`
+    /// unless the notation is compact.
+    pub significant_digits: Option<usize>,
+    /// Groups the integer digits by thousands. Defaults to [`NumberLocale::use_grouping`].
+    pub thousands_separator: Option<bool>,
+    /// Overrides [`NumberLocale::decimal_separator`].
+    pub decimal_separator: Option<char>,
+    /// When to show the sign. Defaults to [`SignDisplay::Auto`].
+    pub sign_display: Option<SignDisplay>,
+    /// Multiplies the value by 100 and appends the percent sign.
+    pub percent: Option<bool>,
+    /// Use [`Notation::Compact`] to abbreviate large numbers like "1.2k".
+    pub notation: Option<Notation>,
+    /// Overrides the locale of the table, for example `locale = "de"`.
+    pub locale: Option<NumberLocale>,
+    /// Shows the value as an amount of this currency. An ISO 4217 code like `"EUR"` is replaced by its
+    /// symbol which is placed according to the locale. Defaults the precision to the minor units of the currency.
+    pub currency: Option<&'static str>,
+    /// Appended to the value, for example `"kg"`.
+    pub unit: Option<&'static str>,
 }
 
 /// A value that can be rendered as part of a table, required for types if the [`crate::DefaultTableCellRenderer()`] is used
@@ -14,6 +57,71 @@ pub trait CellValue<M: ?Sized = ()> {
 
     /// This is called to actually render the value. The parameter `options` is filled by the `#[table(format(...))]` macro attribute or `Default::default()` if omitted.
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView;
+
+    /// How the value should be aligned in its cell. The [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer())
+    /// adds the classes returned by [`TableClassesProvider::cell_alignment`](crate::TableClassesProvider::cell_alignment)
+    /// for it. Numbers are aligned at the end so their digits line up.
+    #[allow(unused_variables)]
+    fn alignment(options: &Self::RenderOptions) -> CellAlignment {
+        CellAlignment::Start
+    }
+
+    /// Called by the [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()) before the value is
+    /// rendered with the [`NumberLocale`] that was provided via context when the table was created.
+    /// Numbers use it unless their `options` have their own `locale`.
+    #[allow(unused_variables)]
+    fn set_default_locale(options: &mut Self::RenderOptions, locale: NumberLocale) {}
+
+    /// The plain text of the value formatted with `options`, i.e. [`TextCellValue::format_text`] for the
+    /// types that implement it. Returns `None` by default which means that the text isn't known.
+    ///
+    /// The [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()) uses this to provide the text of
+    /// a cell for the clipboard and exports. Like [`Self::render_value`] it takes the value.
+    #[allow(unused_variables)]
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// The typed spreadsheet cell of the value formatted with `options`, i.e.
+    /// [`XlsxCellValue::xlsx_cell`](crate::xlsx::XlsxCellValue::xlsx_cell) for the types that implement it.
+    /// Returns `None` by default which means that the text of [`Self::to_text`] is exported.
+    #[cfg(feature = "xlsx")]
+    #[allow(unused_variables)]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        None
+    }
+}
+
+/// The horizontal alignment of the content of a cell.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CellAlignment {
+    /// Left in left-to-right languages. The default for text.
+    #[default]
+    Start,
+    /// Right in left-to-right languages. Used for numbers, amounts and quantities.
+    End,
+}
+
+/// A [`CellValue`] that can be formatted as plain text, for example for exports, tooltips, the clipboard or search.
+///
+/// [`Self::format_text`] returns exactly the text that [`CellValue::render_value`] displays with the same options.
+/// All implementations in this crate render their values by calling [`Self::format_text`].
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// let options = NumberRenderOptions {
+///     precision: Some(2),
+///     ..Default::default()
+/// };
+/// assert_eq!(12.3456.format_text(&options), "12.35");
+/// assert_eq!(true.format_text(&()), "true");
+/// ```
+pub trait TextCellValue<M: ?Sized = ()>: CellValue<M> {
+    /// Returns the text of this value formatted with `options` like the default cell renderer shows it.
+    fn format_text(&self, options: &Self::RenderOptions) -> String;
 }
 
 impl<V> CellValue for V
@@ -25,27 +133,74 @@ where
     fn render_value(self, _options: Self::RenderOptions) -> impl IntoView {
         self
     }
+
+    /// Views are rendered to HTML. If that is only text like for strings, it's returned as it is.
+    /// Views with elements have no text.
+    fn to_text(self, _options: &Self::RenderOptions) -> Option<String> {
+        let is_empty = self.html_len() == 0;
+        let html = self.to_html();
+
+        // text is escaped, so a `<` starts an element or a comment
+        if html.contains('<') {
+            return None;
+        }
+        // empty text is rendered as a space
+        if is_empty && html == " " {
+            return Some(String::new());
+        }
+
+        Some(
+            html.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&amp;", "&"),
+        )
+    }
 }
 
 macro_rules! viewable_primitive {
+  (xlsx: $($child_type:ty),* $(,)?) => {
+    $(
+      viewable_primitive!(@impl $child_type {
+        #[cfg(feature = "xlsx")]
+        fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+            Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
+        }
+      });
+    )*
+  };
   ($($child_type:ty),* $(,)?) => {
     $(
+      viewable_primitive!(@impl $child_type {});
+    )*
+  };
+  (@impl $child_type:ty { $($xlsx:tt)* }) => {
       impl CellValue<$child_type> for $child_type {
         type RenderOptions = ();
 
         #[inline(always)]
-        fn render_value(self, _options: Self::RenderOptions) -> impl IntoView {
+        fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
+            self.format_text(&options)
+        }
+
+        fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+            Some(self.format_text(options))
+        }
+
+        $($xlsx)*
+      }
+
+      impl TextCellValue<$child_type> for $child_type {
+        fn format_text(&self, _options: &Self::RenderOptions) -> String {
             self.to_string()
         }
       }
-    )*
   };
 }
 
+viewable_primitive![xlsx: bool, char];
+
 viewable_primitive![
     &String,
-    char,
-    bool,
     std::net::IpAddr,
     std::net::SocketAddr,
     std::net::SocketAddrV4,
@@ -70,23 +225,37 @@ viewable_primitive![
 ];
 
 macro_rules! viewable_number_primitive {
-  ($($child_type:ty),* $(,)?) => {
+  ($integer:literal: $($child_type:ty),* $(,)?) => {
     $(
       impl CellValue<$child_type> for $child_type {
         type RenderOptions = NumberRenderOptions;
 
         #[inline(always)]
         fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.precision.as_ref() {
-            view! {
-                <>{format!("{:.value$}", self)}</>
-            }
+            self.format_text(&options)
+        }
+
+        fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+            Some(self.format_text(options))
+        }
+
+        #[cfg(feature = "xlsx")]
+        fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+            Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
+        }
+
+        fn alignment(_options: &Self::RenderOptions) -> CellAlignment {
+            CellAlignment::End
         }
-        else {
-            view! {
-                <>{self.to_string()}</>
-            }
+
+        fn set_default_locale(options: &mut Self::RenderOptions, locale: NumberLocale) {
+            options.locale.get_or_insert(locale);
         }
+      }
+
+      impl TextCellValue<$child_type> for $child_type {
+        fn format_text(&self, options: &Self::RenderOptions) -> String {
+            format_number(self, $integer, options)
         }
       }
     )*
@@ -94,5 +263,18 @@ macro_rules! viewable_number_primitive {
 }
 
 viewable_number_primitive![
-    usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128, f32, f64,
+    true: usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128,
 ];
+viewable_number_primitive![false: f32, f64];
+
+impl TextCellValue for String {
+    fn format_text(&self, _options: &Self::RenderOptions) -> String {
+        self.clone()
+    }
+}
+
+impl TextCellValue for &'static str {
+    fn format_text(&self, _options: &Self::RenderOptions) -> String {
+        self.to_string()
+    }
+}
@@ -20,6 +20,21 @@ macro_rules! chrono_cell_value_impl {
             type RenderOptions = RenderChronoOptions;
 
             fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
+                self.format_text(&options)
+            }
+
+            fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+                Some(self.format_text(options))
+            }
+
+            #[cfg(feature = "xlsx")]
+            fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+                Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
+            }
+        }
+
+        impl TextCellValue<$ty> for $ty {
+            fn format_text(&self, options: &Self::RenderOptions) -> String {
                 if let Some(value) = options.string.as_ref() {
                     self.format(&value).to_string()
                 } else {
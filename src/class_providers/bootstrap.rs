@@ -1,4 +1,4 @@
-use crate::TableClassesProvider;
+use crate::{CellAlignment, TableClassesProvider};
 
 #[derive(Clone, Copy)]
 pub struct BootstrapClassesPreset;
@@ -14,5 +14,21 @@ impl TableClassesProvider for BootstrapClassesPreset {
         format!("{} {}", active, template_classes)
     }
 
+    fn cell_alignment(&self, alignment: CellAlignment) -> String {
+        match alignment {
+            CellAlignment::Start => "",
+            CellAlignment::End => "text-end",
+        }
+        .to_string()
+    }
+
+    fn selected_cell(&self) -> String {
+        "table-active".to_string()
+    }
+
+    fn selection_checkbox(&self) -> String {
+        "form-check-input".to_string()
+    }
+
     // TODO : skeleton loading
 }
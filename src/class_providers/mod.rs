@@ -1,7 +1,7 @@
 mod bootstrap;
 mod tailwind;
 
-use crate::ColumnSort;
+use crate::{CellAlignment, ColumnSort};
 pub use bootstrap::*;
 pub use tailwind::*;
 
@@ -73,9 +73,44 @@ pub trait TableClassesProvider {
 
     /// Get the classes for the body cells.
     /// The `macro_class` parameter contains the classes specified in the `class` macro attribute of the field.
+    /// Cells that are part of a [`CellSelection`] additionally get the classes of [`Self::selected_cell`].
+    /// Cells rendered by the [`DefaultTableCellRenderer`] additionally get the classes of [`Self::cell_alignment`].
     fn cell(&self, macro_class: &str) -> String {
         macro_class.to_string()
     }
+
+    #[allow(unused_variables)]
+    /// Get the classes that align the content of a body cell. The `alignment` is the hint given by
+    /// [`CellValue::alignment`] for the value of the cell, for example [`CellAlignment::End`] for numbers.
+    fn cell_alignment(&self, alignment: CellAlignment) -> String {
+        String::new()
+    }
+
+    /// Get the classes that are added to the body cells that are part of the [`CellSelection`]
+    /// given to the `cell_selection` prop of [`TableContent`]. They are removed again when the
+    /// cell is deselected. See [`CellSelectionContext`] for custom cell renderers.
+    fn selected_cell(&self) -> String {
+        "".to_string()
+    }
+
+    /// Get the classes for the head cell of the selection column.
+    /// Only used if the `selection_column` prop of [`TableContent`] is `true`.
+    fn selection_head_cell(&self) -> String {
+        "".to_string()
+    }
+
+    #[allow(unused_variables)]
+    /// Get the classes for the body cells of the selection column.
+    /// The `row_index` parameter contains the index of the row. The first row has index 0.
+    /// The `selected` parameter indicates whether the row is selected.
+    fn selection_cell(&self, row_index: usize, selected: bool) -> String {
+        "".to_string()
+    }
+
+    /// Get the classes for the checkboxes of the selection column (head and body).
+    fn selection_checkbox(&self) -> String {
+        "".to_string()
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -1,4 +1,4 @@
-use crate::{ColumnSort, TableClassesProvider};
+use crate::{CellAlignment, ColumnSort, TableClassesProvider};
 
 #[derive(Clone, Copy)]
 pub struct TailwindClassesPreset;
@@ -71,4 +71,28 @@ impl TableClassesProvider for TailwindClassesPreset {
     fn cell(&self, template_classes: &str) -> String {
         format!("{} {}", "px-5 py-2", template_classes)
     }
+
+    fn cell_alignment(&self, alignment: CellAlignment) -> String {
+        match alignment {
+            CellAlignment::Start => "",
+            CellAlignment::End => "text-right tabular-nums",
+        }
+        .to_string()
+    }
+
+    fn selected_cell(&self) -> String {
+        "bg-sky-100 dark:bg-sky-900".to_string()
+    }
+
+    fn selection_head_cell(&self) -> String {
+        "w-px pl-5 py-2".to_string()
+    }
+
+    fn selection_cell(&self, _row_index: usize, _selected: bool) -> String {
+        "w-px pl-5 py-2".to_string()
+    }
+
+    fn selection_checkbox(&self) -> String {
+        "cursor-pointer align-middle".to_string()
+    }
 }
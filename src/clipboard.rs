@@ -0,0 +1,508 @@
+use crate::{CellValue, TableRow};
+use leptos::prelude::*;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Implement this for your row type to override the texts of its cells that are copied to the clipboard
+/// or exported.
+///
+/// This isn't needed for rows that `#[derive(TableRow)]`. By default the text of a cell is the value
+/// formatted with the options of `#[table(format(...))]` (see [`CellTextGetter::from_cell_renderers`]).
+/// Only cells with a custom renderer are empty. Use [`cell_text_of`] for the other columns.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos::prelude::*;
+/// #[derive(TableRow, Clone)]
+/// #[table]
+/// struct Product {
+///     name: String,
+///     #[table(format(precision = 2usize))]
+///     price: f64,
+///     #[table(renderer = "TagsCellRenderer")]
+///     tags: Vec<String>,
+/// }
+///
+/// impl TextRow<usize> for Product {
+///     fn cell_text(&self, column: usize) -> String {
+///         match column {
+///             2 => self.tags.join(", "),
+///             column => cell_text_of(self, column),
+///         }
+///     }
+/// }
+/// #
+/// # #[component]
+/// # fn TagsCellRenderer(
+/// #     class: String,
+/// #     value: Signal<Vec<String>>,
+/// #     row: RwSignal<Product>,
+/// #     index: usize,
+/// # ) -> impl IntoView {
+/// #     view! { <td class=class>{move || value.get().join(", ")}</td> }
+/// # }
+/// ```
+pub trait TextRow<Column> {
+    /// Returns the formatted text of the given column.
+    fn cell_text(&self, column: Column) -> String;
+}
+
+/// Returns the formatted text of a cell. Pass this to the `cell_text` prop of [`TableContent`] to
+/// enable copying the selected rows or cells to the clipboard.
+pub struct CellTextGetter<Row, Column>(Arc<CellsFn<Row, Column, String>>);
+
+impl<Row, Column> Clone for CellTextGetter<Row, Column> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<F, Row, Column> From<F> for CellTextGetter<Row, Column>
+where
+    F: Fn(&Row, Column) -> String + Send + Sync + 'static,
+    Row: 'static,
+    Column: Copy + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(cells_of_fn(f))
+    }
+}
+
+impl<Row, Column> CellTextGetter<Row, Column> {
+    /// Uses the cell renderers to get the texts like [`cell_text_of`]. This is the default.
+    ///
+    /// The cells of a column are only built once per copy or export and then reused for every row.
+    pub fn from_cell_renderers() -> Self
+    where
+        Row: TableRow<Column> + Clone + Send + Sync + 'static,
+        Column: Copy + Send + Sync + 'static,
+    {
+        Self(cells_of_renderers(CellTextProbe))
+    }
+
+    /// Uses [`TextRow::cell_text`] to get the text of a cell.
+    pub fn from_text_row() -> Self
+    where
+        Row: TextRow<Column> + 'static,
+        Column: Copy + 'static,
+    {
+        Self(cells_of_fn(Row::cell_text))
+    }
+
+    /// Returns the text of a single cell. The table and the exporters get the cells of many rows at once.
+    pub fn run(&self, row: &Row, column: Column) -> String {
+        (self.0)(&[column])(row).pop().unwrap_or_default()
+    }
+
+    /// Returns a function that returns the texts of the `columns` of a row. Call this once per copy
+    /// or export and apply the returned function to every row.
+    pub(crate) fn cells(&self, columns: &[Column]) -> RowCells<Row, String> {
+        (self.0)(columns)
+    }
+}
+
+/// Returns the cells of a row for the columns it was created for. See [`CellTextGetter::cells`].
+pub(crate) type RowCells<Row, T> = Box<dyn FnMut(&Row) -> Vec<T>>;
+
+pub(crate) type CellsFn<Row, Column, T> = dyn Fn(&[Column]) -> RowCells<Row, T> + Send + Sync;
+
+/// Calls `f` for every cell.
+pub(crate) fn cells_of_fn<Row, Column, T>(
+    f: impl Fn(&Row, Column) -> T + Send + Sync + 'static,
+) -> Arc<CellsFn<Row, Column, T>>
+where
+    Row: 'static,
+    Column: Copy + 'static,
+    T: 'static,
+{
+    let f = Arc::new(f);
+
+    Arc::new(move |columns| {
+        let f = Arc::clone(&f);
+        let columns = columns.to_vec();
+
+        Box::new(move |row| columns.iter().map(|column| f(row, *column)).collect())
+    })
+}
+
+/// Builds the cells with [`ProbedCells`] for the first row and reuses them for the others.
+pub(crate) fn cells_of_renderers<Row, Column, P, T>(
+    probe: fn(StoredValue<Option<CellReader<T>>>) -> P,
+) -> Arc<CellsFn<Row, Column, T>>
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+    T: Default + Send + Sync + 'static,
+{
+    Arc::new(move |columns| {
+        let columns = columns.to_vec();
+        let mut cells = None::<ProbedCells<Row, T>>;
+
+        Box::new(move |row| {
+            let cells = match &mut cells {
+                Some(cells) => {
+                    cells.set_row(row);
+                    cells
+                }
+                None => cells.insert(ProbedCells::new(row, &columns, probe)),
+            };
+
+            cells.read().map(Option::unwrap_or_default).collect()
+        })
+    })
+}
+
+/// Reads the value of a cell that has been built with a probe. Written by the
+/// [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()).
+pub(crate) type CellReader<T> = Arc<dyn Fn() -> Option<T> + Send + Sync>;
+
+/// Provided as context while the cells are built for [`CellTextGetter::from_cell_renderers`]. The
+/// [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()) writes how to get the text of its value into it.
+#[derive(Copy, Clone)]
+pub(crate) struct CellTextProbe(StoredValue<Option<CellReader<String>>>);
+
+impl CellTextProbe {
+    pub(crate) fn set<T, M>(&self, value: Signal<T>, options: T::RenderOptions)
+    where
+        T: CellValue<M> + Clone + Send + Sync + 'static,
+        M: ?Sized,
+    {
+        self.0.set_value(Some(Arc::new(move || {
+            value.with_untracked(|value| value.clone().to_text(&options))
+        })));
+    }
+}
+
+/// The cells of some columns built with [`TableRow::cell_renderer_for_column`] without rendering them.
+/// A probe is provided as context while they are built and the cell renderers write a [`CellReader`]
+/// into it. The row of the cells can be replaced, so the cells only have to be built once for many rows.
+pub(crate) struct ProbedCells<Row: Send + Sync + 'static, T> {
+    owner: Owner,
+    row: RwSignal<Row>,
+    cells: Vec<Option<CellReader<T>>>,
+}
+
+impl<Row, T> ProbedCells<Row, T>
+where
+    Row: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new<Column, P>(
+        row: &Row,
+        columns: &[Column],
+        probe: impl FnOnce(StoredValue<Option<CellReader<T>>>) -> P,
+    ) -> Self
+    where
+        Row: TableRow<Column>,
+        Column: Copy + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        let owner = Owner::new();
+
+        let (row, cells) = owner.with(|| {
+            let reader = StoredValue::new(None);
+            provide_context(probe(reader));
+
+            let row = RwSignal::new(row.clone());
+            let cells = columns
+                .iter()
+                .map(|column| {
+                    drop(Row::cell_renderer_for_column(row, *column, String::new()));
+                    reader.try_update_value(Option::take).flatten()
+                })
+                .collect();
+
+            (row, cells)
+        });
+
+        Self { owner, row, cells }
+    }
+
+    pub(crate) fn set_row(&self, row: &Row) {
+        self.row.set(row.clone());
+    }
+
+    /// The values of the cells for the current row. `None` for cells that aren't rendered by the
+    /// [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()).
+    pub(crate) fn read(&self) -> impl Iterator<Item = Option<T>> + '_ {
+        self.cells
+            .iter()
+            .map(|cell| cell.as_ref().and_then(|cell| cell()))
+    }
+}
+
+impl<Row: Send + Sync + 'static, T> Drop for ProbedCells<Row, T> {
+    fn drop(&mut self) {
+        self.owner.cleanup();
+    }
+}
+
+/// Returns the text of the cell of `column` like it is shown in the table, i.e. the value formatted by
+/// [`TextCellValue::format_text`](crate::TextCellValue::format_text) with the options given to
+/// `#[table(format(...))]`. The cell is built with [`TableRow::cell_renderer_for_column`] but never
+/// rendered. Returns an empty string if the cell isn't rendered by the [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer())
+/// or its value can't be represented as text (see [`CellValue::to_text`](crate::CellValue::to_text)).
+///
+/// This builds the cell for this call only. [`CellTextGetter::from_cell_renderers`] reuses the cells for many rows.
+pub fn cell_text_of<Row, Column>(row: &Row, column: Column) -> String
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    ProbedCells::new(row, &[column], CellTextProbe)
+        .read()
+        .next()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Rows of formatted cell texts that can be serialized for the clipboard or an export.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// let grid = TextGrid {
+///     rows: vec![
+///         vec!["Name".to_string(), "Price".to_string()],
+///         vec!["Tea, green".to_string(), "3.50".to_string()],
+///     ],
+/// };
+///
+/// assert_eq!(grid.to_tsv(), "Name\tPrice\r\nTea, green\t3.50\r\n");
+/// assert_eq!(grid.to_csv(), "Name,Price\r\n\"Tea, green\",3.50\r\n");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextGrid {
+    pub rows: Vec<Vec<String>>,
+}
+
+impl TextGrid {
+    /// Tab separated values. This is what spreadsheet applications expect as plain text in
+    /// the clipboard. Cells containing tabs, line breaks or quotes are quoted.
+    pub fn to_tsv(&self) -> String {
+        self.to_delimited('\t')
+    }
+
+    /// Comma separated values according to RFC 4180.
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',')
+    }
+
+    /// An HTML `<table>` fragment. Spreadsheet applications keep the columns when this is pasted.
+    pub fn to_html(&self) -> String {
+        let mut html = "<table>".to_string();
+
+        for row in &self.rows {
+            html.push_str("<tr>");
+            for cell in row {
+                let _ = write!(html, "<td>{}</td>", escape_html(cell));
+            }
+            html.push_str("</tr>");
+        }
+
+        html.push_str("</table>");
+        html
+    }
+
+    fn to_delimited(&self, delimiter: char) -> String {
+        let mut text = String::new();
+
+        for row in &self.rows {
+            write_delimited_row(&mut text, row, delimiter);
+        }
+
+        text
+    }
+}
+
+/// Writes one row of delimited values terminated by CRLF. Fields that contain the delimiter,
+/// quotes or line breaks are quoted and quotes are doubled (RFC 4180).
+pub(crate) fn write_delimited_row<S: AsRef<str>>(out: &mut String, row: &[S], delimiter: char) {
+    for (i, field) in row.iter().enumerate() {
+        if i > 0 {
+            out.push(delimiter);
+        }
+
+        let field = field.as_ref();
+        if field.contains([delimiter, '"', '\r', '\n']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+
+    out.push_str("\r\n");
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnSort, DefaultTableCellRenderer, NumberRenderOptions};
+    use std::collections::VecDeque;
+
+    #[derive(Clone)]
+    struct Product {
+        name: String,
+        price: f64,
+        stock: u32,
+    }
+
+    impl TableRow<usize> for Product {
+        type ClassesProvider = crate::DummyTableClassesProvider;
+
+        const COLUMN_COUNT: usize = 3;
+
+        fn render_row(_: RwSignal<Self>, _: usize, _: RwSignal<Vec<usize>>) -> impl IntoView {}
+
+        fn render_head_row<F>(
+            _: Signal<VecDeque<(usize, ColumnSort)>>,
+            _: F,
+            _: crate::HeadDragHandler<usize>,
+            _: RwSignal<Vec<usize>>,
+        ) -> impl IntoView
+        where
+            F: Fn(crate::TableHeadEvent<usize>) + Send + Clone + 'static,
+        {
+        }
+
+        // like `#[table(format(precision = 2usize))]` on `price` and
+        // `#[table(format(thousands_separator = true))]` on `stock`
+        fn cell_renderer_for_column(
+            row: RwSignal<Self>,
+            column: usize,
+            class: String,
+        ) -> impl IntoView {
+            match column {
+                0 => view! {
+                    <DefaultTableCellRenderer<_, usize, String, ()>
+                        options=()
+                        value=Signal::derive(move || row.read().name.clone())
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+                1 => view! {
+                    <DefaultTableCellRenderer<_, usize, f64, f64>
+                        options=NumberRenderOptions {
+                            precision: Some(2),
+                            ..Default::default()
+                        }
+                        value=Signal::derive(move || row.read().price)
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+                _ => view! {
+                    <DefaultTableCellRenderer<_, usize, u32, u32>
+                        options=NumberRenderOptions {
+                            thousands_separator: Some(true),
+                            ..Default::default()
+                        }
+                        value=Signal::derive(move || row.read().stock)
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+            }
+        }
+
+        fn col_name(column: usize) -> &'static str {
+            ["name", "price", "stock"][column]
+        }
+
+        fn columns() -> &'static [usize] {
+            &[0, 1, 2]
+        }
+    }
+
+    fn product(name: &str, price: f64, stock: u32) -> Product {
+        Product {
+            name: name.to_string(),
+            price,
+            stock,
+        }
+    }
+
+    #[test]
+    fn cell_text_uses_the_format_options() {
+        let owner = Owner::new();
+        owner.set();
+
+        let product = product("Tea", 3.456, 12000);
+
+        assert_eq!(cell_text_of(&product, 0), "Tea");
+        assert_eq!(cell_text_of(&product, 1), "3.46");
+        assert_eq!(cell_text_of(&product, 2), "12,000");
+
+        let getter = CellTextGetter::<Product, usize>::from_cell_renderers();
+        assert_eq!(getter.run(&product, 1), "3.46");
+    }
+
+    #[test]
+    fn cell_text_uses_the_locale_of_the_table() {
+        let owner = Owner::new();
+        owner.set();
+
+        // `use_context::<NumberLocale>` isn't read while the cell is built
+        provide_context(crate::NumberLocale::FR);
+        provide_context(crate::components::TableCellContext {
+            locale: crate::NumberLocale::DE,
+            alignment_class: Callback::new(|_| String::new()),
+        });
+
+        let product = product("Tea", 3.456, 12000);
+
+        assert_eq!(cell_text_of(&product, 1), "3,46");
+        assert_eq!(cell_text_of(&product, 2), "12.000");
+    }
+
+    #[test]
+    fn cells_are_built_once_for_all_rows() {
+        let owner = Owner::new();
+        owner.set();
+
+        let getter = CellTextGetter::<Product, usize>::from_cell_renderers();
+        let mut row_cells = getter.cells(&[2, 0]);
+
+        assert_eq!(row_cells(&product("Tea", 3.5, 1200)), vec!["1,200", "Tea"]);
+        assert_eq!(row_cells(&product("", 1.0, 7)), vec!["7", ""]);
+        assert_eq!(
+            row_cells(&product("Fish & <Chips>", 9.0, 0)),
+            vec!["0", "Fish & <Chips>"]
+        );
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        let grid = TextGrid {
+            rows: vec![vec![
+                "say \"hi\"".to_string(),
+                "a\tb".to_string(),
+                "<b>&</b>".to_string(),
+            ]],
+        };
+
+        assert_eq!(grid.to_tsv(), "\"say \"\"hi\"\"\"\t\"a\tb\"\t<b>&</b>\r\n");
+        assert_eq!(grid.to_csv(), "\"say \"\"hi\"\"\",a\tb,<b>&</b>\r\n");
+        assert_eq!(
+            grid.to_html(),
+            "<table><tr><td>say &quot;hi&quot;</td><td>a\tb</td><td>&lt;b&gt;&amp;&lt;/b&gt;</td></tr></table>"
+        );
+    }
+}
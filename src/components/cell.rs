@@ -1,6 +1,7 @@
 #![allow(unused_variables)]
 
-use crate::CellValue;
+use crate::clipboard::CellTextProbe;
+use crate::{CellAlignment, CellSelectionContext, CellValue, NumberLocale};
 use std::marker::PhantomData;
 
 use leptos::prelude::*;
@@ -9,6 +10,9 @@ use leptos::prelude::*;
 #[component]
 pub fn DefaultTableCellRenderer<Row, Column, T, M>(
     /// The class attribute for the cell element. Generated by the classes provider.
+    /// In a table the classes of [`TableClassesProvider::cell_alignment`](crate::TableClassesProvider::cell_alignment)
+    /// are added to it and, if the cell is selected, the ones of
+    /// [`TableClassesProvider::selected_cell`](crate::TableClassesProvider::selected_cell) (see [`CellSelectionContext`]).
     class: String,
     /// The value to display.
     value: Signal<T>,
@@ -21,10 +25,51 @@ pub fn DefaultTableCellRenderer<Row, Column, T, M>(
 ) -> impl IntoView
 where
     Row: Send + Sync + 'static,
+    Column: Clone + Send + Sync + 'static,
     T: CellValue<M> + Send + Sync + Clone + 'static,
     M: 'static,
 {
+    let mut options = options;
+    let class = match use_context::<TableCellContext>() {
+        Some(context) => {
+            T::set_default_locale(&mut options, context.locale);
+
+            let alignment_class = context.alignment_class.run(T::alignment(&options));
+            if alignment_class.is_empty() {
+                class
+            } else {
+                format!("{class} {alignment_class}")
+            }
+        }
+        None => class,
+    };
+
+    // only built to get the text of the cell, see `CellTextGetter::from_cell_renderers`
+    if let Some(probe) = use_context::<CellTextProbe>() {
+        probe.set(value, options.clone());
+    }
+    #[cfg(feature = "xlsx")]
+    if let Some(probe) = use_context::<crate::xlsx::XlsxCellProbe>() {
+        probe.set(value, options.clone());
+    }
+
+    let cell_selection = use_context::<CellSelectionContext<Column>>();
+    let class = move || match &cell_selection {
+        Some(cell_selection) => cell_selection.cell_class(&class, index.clone()),
+        None => class.clone(),
+    };
+
     view! {
         <td class=class>{move || value.get().render_value(options.clone())}</td>
     }
 }
+
+/// Provided by [`TableContent`](crate::TableContent) to the cells it renders.
+#[derive(Copy, Clone)]
+pub(crate) struct TableCellContext {
+    /// The [`NumberLocale`] that was provided via context when the table was created.
+    pub locale: NumberLocale,
+    /// The classes of [`TableClassesProvider::cell_alignment`](crate::TableClassesProvider::cell_alignment)
+    /// of the classes provider of the table.
+    pub alignment_class: Callback<CellAlignment, String>,
+}
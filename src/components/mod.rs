@@ -1,6 +1,7 @@
 mod cell;
 mod renderer_fn;
 mod row;
+mod selection_column;
 mod table_content;
 mod tbody;
 mod thead;
@@ -8,6 +9,7 @@ mod thead_drag;
 
 pub use cell::*;
 pub use row::*;
+pub use selection_column::*;
 pub use table_content::*;
 pub use tbody::*;
 pub use thead::*;
@@ -1,9 +1,13 @@
-use crate::EventHandler;
+use crate::keyboard_navigation::aria_row_index;
 use crate::table_row::TableRow;
+use crate::{EventHandler, SelectionCheckboxCell, SelectionColumnContext};
 use leptos::prelude::*;
 
 /// The default table row renderer. Uses the `<tr>` element. Please note that this
 /// is **NOT** a `#[component]`.
+///
+/// Custom row renderers should set the `aria-rowindex` attribute like this one does because
+/// keyboard navigation uses it to find the rendered rows.
 #[allow(unused_variables)]
 pub fn DefaultTableRowRenderer<Row, Column>(
     // The class attribute for the row element. Generated by the classes provider.
@@ -23,8 +27,17 @@ where
     Row: TableRow<Column> + 'static,
     Column: Copy + Send + Sync + 'static,
 {
+    let selectable =
+        use_context::<SelectionColumnContext>().is_some_and(|context| context.selectable);
+
     view! {
-        <tr class=class on:click=move |mouse_event| on_select.run(mouse_event)>
+        <tr
+            class=class
+            aria-rowindex=aria_row_index(index)
+            aria-selected=move || selectable.then(|| selected.get().to_string())
+            on:click=move |mouse_event| on_select.run(mouse_event)
+        >
+            {SelectionCheckboxCell(index, selected)}
             {TableRow::render_row(row, index, columns)}
         </tr>
     }
@@ -34,14 +47,24 @@ where
 /// appropriate height. This is used in place of rows that are not shown
 /// before and after the currently visible rows.
 pub fn DefaultRowPlaceholderRenderer(height: Signal<f64>) -> impl IntoView {
-    view! { <tr style:height=move || format!("{}px", height.get()) style="display: block"></tr> }
+    view! {
+        <tr
+            style:height=move || format!("{}px", height.get())
+            style="display: block"
+            aria-hidden="true"
+        ></tr>
+    }
 }
 
 /// The default error row renderer which just displays the error message when
 /// a row fails to load, i.e. when [`TableDataProvider::get_rows`] returns an `Err(..)`.
 #[allow(unused_variables)]
 pub fn DefaultErrorRowRenderer(err: String, index: usize, col_count: usize) -> impl IntoView {
-    view! { <tr><td colspan=col_count>{err}</td></tr> }
+    view! {
+        <tr aria-rowindex=aria_row_index(index)>
+            <td colspan=col_count>{err}</td>
+        </tr>
+    }
 }
 
 /// The default loading row renderer which just displays a loading indicator.
@@ -54,7 +77,7 @@ pub fn DefaultLoadingRowRenderer(
     col_count: usize,
 ) -> impl IntoView {
     view! {
-        <tr class=class>
+        <tr class=class aria-rowindex=aria_row_index(index) aria-busy="true">
             {
                 (0..col_count).map(|col_index| view! {
                     <td class=get_cell_class.run((col_index,))>
@@ -0,0 +1,168 @@
+use crate::{FilterModel, RowSelection, Selection};
+use leptos::prelude::*;
+use std::hash::Hash;
+
+/// Whether none, some or all rows are selected. This is the state of the checkbox in the head
+/// of the selection column.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum SelectionState {
+    #[default]
+    None,
+    Some,
+    All,
+}
+
+impl SelectionState {
+    /// Computes the state of a selection out of the rows that a table shows with `filter`.
+    /// `row_count` is the total number of these rows if known.
+    pub fn of_selection<Row, Column: PartialEq + Send + Sync + 'static>(
+        selection: Selection<Row, Column>,
+        filter: &FilterModel<Column>,
+        row_count: Option<usize>,
+    ) -> Self {
+        match selection {
+            Selection::None => SelectionState::None,
+            Selection::Single(selected_index) => {
+                if selected_index.read().is_some() {
+                    SelectionState::Some
+                } else {
+                    SelectionState::None
+                }
+            }
+            Selection::Multiple(selected_indices) => Self::of_row_selection(
+                &selected_indices.read().shown_with_filter(filter),
+                row_count,
+            ),
+            Selection::MultipleByKey {
+                keys: selected_keys,
+                ..
+            } => Self::of_row_selection(&selected_keys.read().shown_with_filter(filter), row_count),
+        }
+    }
+
+    fn of_row_selection<K: Eq + Hash>(
+        selection: &RowSelection<K>,
+        row_count: Option<usize>,
+    ) -> Self {
+        if selection.is_empty() {
+            return SelectionState::None;
+        }
+
+        match row_count {
+            Some(row_count) if selection.selected_count(row_count) == 0 => SelectionState::None,
+            Some(row_count) if selection.selected_count(row_count) >= row_count => {
+                SelectionState::All
+            }
+            None if selection.is_all() => SelectionState::All,
+            _ => SelectionState::Some,
+        }
+    }
+}
+
+/// Provided as context by [`TableContent`] to render the selection column and the
+/// `aria-selected` attribute of rows. See [`SelectionCheckboxCell`].
+#[derive(Copy, Clone)]
+pub struct SelectionColumnContext {
+    pub(crate) enabled: bool,
+    /// `false` if the selection is `Selection::None`
+    pub(crate) selectable: bool,
+    pub(crate) toggle_row: Callback<(usize, web_sys::MouseEvent)>,
+    pub(crate) cell_class: Callback<(usize, bool), String>,
+    pub(crate) checkbox_class: Signal<String>,
+}
+
+/// Renders the cell with the checkbox of the selection column if the `selection_column` prop
+/// of [`TableContent`] is `true`. Otherwise renders nothing. This is used by [`DefaultTableRowRenderer`].
+/// Call it in your custom row renderer to get the selection column there as well.
+/// Please note that this is **NOT** a `#[component]`.
+#[allow(non_snake_case)]
+pub fn SelectionCheckboxCell(index: usize, selected: Signal<bool>) -> impl IntoView {
+    use_context::<SelectionColumnContext>()
+        .filter(|context| context.enabled)
+        .map(|context| {
+            view! {
+                <td class=move || context.cell_class.run((index, selected.get()))>
+                    <input
+                        type="checkbox"
+                        class=context.checkbox_class
+                        aria-label="Select row"
+                        prop:checked=selected
+                        on:click=move |mouse_event| {
+                            // don't select the row a second time
+                            mouse_event.stop_propagation();
+                            context.toggle_row.run((index, mouse_event));
+                        }
+                    />
+                </td>
+            }
+        })
+}
+
+/// The head cell of the selection column. Renders a checkbox that shows whether none, some
+/// or all rows are selected and selects or deselects all rows when clicked. For single selection
+/// the cell is empty.
+pub(crate) fn selection_head_cell<Row, Column>(
+    selection: Selection<Row, Column>,
+    state: Signal<SelectionState>,
+    toggle_all: Callback<()>,
+    class: String,
+    checkbox_class: Signal<String>,
+) -> impl IntoView {
+    let checkbox = matches!(
+        selection,
+        Selection::Multiple(_) | Selection::MultipleByKey { .. }
+    )
+    .then(|| {
+        view! {
+            <input
+                type="checkbox"
+                class=checkbox_class
+                aria-label="Select all rows"
+                prop:checked=move || state.get() == SelectionState::All
+                prop:indeterminate=move || state.get() == SelectionState::Some
+                on:click=move |_| toggle_all.run(())
+            />
+        }
+    });
+
+    view! { <th class=class>{checkbox}</th> }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn state_of_row_selection() {
+        let none = RowSelection::<usize>::default();
+        let some = RowSelection::Only(HashSet::from([1, 2]));
+        let all = RowSelection::<usize>::AllExcept(HashSet::new());
+        let all_but_one = RowSelection::AllExcept(HashSet::from([1]));
+
+        assert_eq!(
+            SelectionState::of_row_selection(&none, Some(3)),
+            SelectionState::None
+        );
+        assert_eq!(
+            SelectionState::of_row_selection(&some, Some(3)),
+            SelectionState::Some
+        );
+        assert_eq!(
+            SelectionState::of_row_selection(&some, Some(2)),
+            SelectionState::All
+        );
+        assert_eq!(
+            SelectionState::of_row_selection(&all, None),
+            SelectionState::All
+        );
+        assert_eq!(
+            SelectionState::of_row_selection(&all_but_one, None),
+            SelectionState::Some
+        );
+        assert_eq!(
+            SelectionState::of_row_selection(&all_but_one, Some(1)),
+            SelectionState::None
+        );
+    }
+}
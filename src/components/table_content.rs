@@ -1,25 +1,33 @@
 // leptos-struct-table/src/components/table_content.rs
 
-#![allow(clippy::await_holding_refcell_ref)]
-
+use crate::clipboard::RowCells;
+use crate::components::cell::TableCellContext;
 use crate::components::renderer_fn::renderer_fn;
+use crate::data_provider_cell::DataProviderCell;
+use crate::keyboard_navigation::{
+    CellPosition, cell_position_of, find_cell, find_first_cell, focus_cell, is_editable_target,
+    next_cell_position, set_tabbable_cell,
+};
 use crate::loaded_rows::{LoadedRows, RowState};
-use crate::selection::Selection;
+use crate::loading_queue::{ChunkKind, LoadingQueue};
+use crate::selection::{RowSelection, Selection};
 use crate::table_row::TableRow;
+use crate::{CellIndex, CellSelection, CellSelectionContext, CellTextGetter, TextGrid};
 use crate::{
     ChangeEvent, ColumnSort, DefaultErrorRowRenderer, DefaultLoadingRowRenderer,
     DefaultRowPlaceholderRenderer, DefaultTableBodyRenderer, DefaultTableHeadRenderer,
     DefaultTableHeadRowRenderer, DefaultTableRowRenderer, DisplayStrategy, EventHandler,
-    HeadDragHandler, ReloadController, RowReader, SelectionChangeEvent, SortingMode,
-    TableClassesProvider, TableDataProvider, TableHeadEvent,
+    FilterModel, HeadDragHandler, NumberLocale, ReloadController, RowKeyGetter, RowReader,
+    SelectionChangeEvent, SortingMode, TableClassesProvider, TableDataProvider, TableHeadEvent,
 };
+use crate::{SelectionColumnContext, SelectionState, selection_head_cell};
 use leptos::prelude::*;
 use leptos::tachys::view::any_view::AnyView;
 use leptos::task::spawn_local;
 use leptos_use::core::IntoElementMaybeSignal;
 use leptos_use::{
     UseElementSizeOptions, UseElementSizeReturn, UseScrollOptions, UseScrollReturn,
-    use_debounce_fn, use_element_size_with_options, use_scroll_with_options,
+    use_debounce_fn, use_element_size_with_options, use_event_listener, use_scroll_with_options,
 };
 use std::cell::RefCell;
 use std::collections::{HashSet, VecDeque};
@@ -87,15 +95,50 @@ pub fn TableContent<Row, Column, DataP, Err, ClsP, ScrollEl, ScrollM>(
     /// - `None` - No selection (default)
     /// - `Single` - Single selection
     /// - `Multiple` - Multiple selection
+    /// - `MultipleByKey` - Multiple selection by row key that is kept when sorting, filtering or reloading
     ///
     /// Please see [`Selection`] for more information and check out the
     /// [selectable example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/selectable/src/main.rs).
     #[prop(optional, into)]
-    selection: Selection,
+    selection: Selection<Row, Column>,
     /// Event handler callback for when the selection changes.
     /// See the [selectable example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/selectable/src/main.rs) for details.
     #[prop(optional, into)]
     on_selection_change: EventHandler<SelectionChangeEvent<Row>>,
+    /// Returns a key that identifies the record shown in a row independently of its position in the
+    /// table, for example its primary key. It's used to key the rendered rows, so a row is rendered from
+    /// scratch if a different record ends up at its index, and it is passed along in [`ChangeEvent`] and
+    /// [`SelectionChangeEvent`]. It's also used to identify the rows of a [`Selection::MultipleByKey`].
+    ///
+    /// If not given, the key getter of a [`Selection::MultipleByKey`] is used. Otherwise rows are only
+    /// identified by their index.
+    #[prop(optional, into)]
+    row_key: Option<RowKeyGetter<Row>>,
+    /// Renders a column with a checkbox in front of every row to select it. The checkbox in the
+    /// head shows if none, some or all rows are selected and selects or deselects all rows when clicked.
+    /// Has no effect if `selection` is `Selection::None`. Defaults to `false`.
+    ///
+    /// The classes of this column are given by [`TableClassesProvider::selection_cell`],
+    /// [`TableClassesProvider::selection_head_cell`] and [`TableClassesProvider::selection_checkbox`].
+    /// Custom row renderers can render the checkbox cell with [`SelectionCheckboxCell`].
+    #[prop(optional)]
+    selection_column: bool,
+    /// Enables spreadsheet-like selection of rectangular cell ranges. This works independently of `selection`
+    /// but clicking a cell doesn't select its row anymore. Use the `selection_column` or the keyboard for that.
+    /// The selected cells get the classes of [`TableClassesProvider::selected_cell`]. Custom cell renderers
+    /// can add them with [`CellSelectionContext`].
+    /// Please see [`CellSelection`] for how cells are selected.
+    #[prop(optional, into)]
+    cell_selection: Option<RwSignal<CellSelection<Column>>>,
+    /// Returns the text of a cell. The selected cells (see `cell_selection`) or otherwise the
+    /// selected rows can be copied with Ctrl+C (Cmd+C) while the focus is inside the table body.
+    /// The clipboard receives tab separated values and an HTML table so spreadsheet applications
+    /// keep the columns. Only loaded rows are copied.
+    ///
+    /// Defaults to [`CellTextGetter::from_cell_renderers`] which formats the values with the options of
+    /// `#[table(format(...))]`. Please see [`TextRow`] to override it.
+    #[prop(optional, into)]
+    cell_text: Option<CellTextGetter<Row, Column>>,
     /// Renderer function for the table head. Defaults to [`DefaultTableHeadRenderer`]. For a full example see the
     /// [custom_renderers_svg example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/custom_renderers_svg/src/main.rs).
     #[prop(default = DefaultTableHeadRenderer.into(), into)]
@@ -150,6 +193,10 @@ pub fn TableContent<Row, Column, DataP, Err, ClsP, ScrollEl, ScrollM>(
     /// Please see the [simple example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/simple/src/main.rs).
     #[prop(default = RwSignal::new(VecDeque::new()), into)]
     sorting: RwSignal<VecDeque<(Column, ColumnSort)>>,
+    /// The filters to apply to the table. Every time this changes, [`TableDataProvider::set_filter`]
+    /// is called and the table is reloaded. Please see [`FilterModel`] for more information.
+    #[prop(default = RwSignal::new(FilterModel::default()), into)]
+    filter: RwSignal<FilterModel<Column>>,
     /// The sorting mode to use. Defaults to `MultiColumn`. Please note that
     /// this to have any effect you have to add the macro attribute `#[table(sortable)]`
     /// to your struct.
@@ -173,7 +220,8 @@ pub fn TableContent<Row, Column, DataP, Err, ClsP, ScrollEl, ScrollM>(
     /// for how to use.
     #[prop(optional)]
     drag_handler: HeadDragHandler<Column>,
-    /// Allows to manually trigger a reload.
+    /// Allows to manually trigger a reload. Use [`ReloadController::soft_reload`] to refresh the
+    /// visible rows in the background without showing them as loading.
     ///
     /// See the [paginated_rest_datasource example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/paginated_rest_datasource/src/main.rs)
     /// for how to use.
@@ -193,9 +241,17 @@ pub fn TableContent<Row, Column, DataP, Err, ClsP, ScrollEl, ScrollM>(
     /// loading rows.
     #[prop(optional)]
     loading_row_display_limit: Option<usize>,
+    /// The maximum number of loaded rows that are kept in the cache. Defaults to `None` which means unlimited.
+    /// Once there are more, the loaded rows furthest away from the visible rows are evicted and
+    /// loaded again when they become visible again. Edited rows and rows that have been selected
+    /// explicitly are never evicted. Rows that are only selected because all (matching) rows have
+    /// been selected are evicted like the others, since the selection doesn't need them to be loaded.
+    /// Use this for very large tables where users scroll through many rows.
+    #[prop(optional)]
+    max_loaded_rows: Option<usize>,
     /// Provides access to the data rows.
     #[prop(optional)]
-    row_reader: RowReader<Row>,
+    row_reader: RowReader<Row, Column>,
 
     #[prop(optional)] _marker: PhantomData<(Err, ScrollM)>,
 ) -> impl IntoView
@@ -209,7 +265,16 @@ where
     ScrollM: 'static,
 {
     let on_change = StoredValue::new(on_change);
-    let rows = Rc::new(RefCell::new(rows));
+    let rows = DataProviderCell::new(rows);
+    let row_key = row_key.or_else(|| selection.row_key());
+    let key_of = move |row: RwSignal<Row>| {
+        row_key.and_then(|row_key| row.with_untracked(|row| row_key.run(row)))
+    };
+    // the selection identifies its rows with its own key getter even if `row_key` is different
+    let selection_key_of = move |row: RwSignal<Row>| match selection.row_key() {
+        Some(row_key) => row.with_untracked(|row| row_key.run(row)),
+        None => key_of(row),
+    };
 
     let class_provider = ClsP::new();
 
@@ -228,6 +293,12 @@ where
             loaded_rows.read()[index].clone()
         }));
 
+    let _ = row_reader
+        .get_cache_metrics
+        .replace(Box::new(move || loaded_rows.read().metrics()));
+
+    row_reader.connect(&rows, columns);
+
     let first_selected_index = RwSignal::new(None::<usize>);
 
     let (row_count, set_row_count) = signal(None::<usize>);
@@ -249,8 +320,7 @@ where
                 let set_known_row_count = set_known_row_count.clone();
 
                 async move {
-                    // TODO: can we avoid this?
-                    let row_count = rows.borrow().row_count().await;
+                    let row_count = rows.provider().await.row_count().await;
 
                     // check if this component was disposed of
                     if sorting.try_with_untracked(|_| {}).is_none() {
@@ -268,12 +338,20 @@ where
         }
     };
 
+    let loading_queue = Rc::new(RefCell::new(LoadingQueue::new(
+        DataP::MAX_CONCURRENT_REQUESTS,
+    )));
+
     let (reload_count, set_reload_count) = signal(0_usize);
     let clear = {
         let load_row_count = load_row_count.clone();
+        let loading_queue = Rc::clone(&loading_queue);
 
         move |clear_row_count: bool| {
-            selection.clear();
+            loading_queue.borrow_mut().clear();
+            if !selection.is_persistent() {
+                selection.clear();
+            }
             first_selected_index.set(None);
             LoadedRows::<Row>::clear(&mut loaded_rows.write());
 
@@ -296,13 +374,33 @@ where
     Effect::new({
         let clear = clear.clone();
         let rows = Rc::clone(&rows);
+        let row_reader = row_reader.clone();
 
         move || {
-            let sorting = sorting.read();
-            if let Ok(mut rows) = rows.try_borrow_mut() {
-                rows.set_sorting(&sorting);
-                clear(false);
-            };
+            let sorting = sorting.get();
+            // applied before the next request even if a request is still running
+            rows.mutate(move |rows| rows.set_sorting(&sorting));
+            row_reader.sorting_or_filter_changed();
+            clear(false);
+        }
+    });
+
+    Effect::new({
+        let clear = clear.clone();
+        let rows = Rc::clone(&rows);
+        let row_reader = row_reader.clone();
+
+        move |prev: Option<()>| {
+            let filter = filter.get();
+            // nothing is filtered on mount so there is no need to reload
+            if prev.is_none() && filter.is_empty() {
+                return;
+            }
+            // applied before the next request even if a request is still running
+            rows.mutate(move |rows| rows.set_filter(&filter));
+            row_reader.sorting_or_filter_changed();
+            // the filter changes the row count as well
+            clear(true);
         }
     });
 
@@ -312,22 +410,144 @@ where
         move || {
             // triggered when `ReloadController::reload()` is called
             reload_controller.track();
-            rows.borrow().track();
+            rows.with(|rows| rows.track());
             clear(true);
         }
     });
 
     let selected_indices = match selection {
-        Selection::None => Signal::stored(HashSet::new()),
+        Selection::None => Signal::stored(RowSelection::default()),
         Selection::Single(selected_index) => Signal::derive(move || {
             selected_index
                 .get()
-                .map(|i| HashSet::from([i]))
+                .map(|i| RowSelection::Only(HashSet::from([i])))
                 .unwrap_or_default()
         }),
-        Selection::Multiple(selected_indices) => selected_indices.into(),
+        Selection::Multiple(selected_indices) => {
+            Memo::new(move |_| selected_indices.read().shown_with_filter(&filter.read())).into()
+        }
+        Selection::MultipleByKey {
+            keys: selected_keys,
+            ..
+        } => {
+            // the selection in terms of the indices of the loaded rows
+            Memo::new(move |_| {
+                let selected_keys = selected_keys.read().shown_with_filter(&filter.read());
+                let (RowSelection::Only(keys) | RowSelection::AllExcept(keys)) = &selected_keys
+                else {
+                    unreachable!("only `Only` and `AllExcept` are shown");
+                };
+
+                let indices = loaded_rows
+                    .read()
+                    .iter_loaded()
+                    .filter(|(_, row)| {
+                        selection_key_of(*row).is_some_and(|key| keys.contains(&key))
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+
+                match selected_keys {
+                    RowSelection::AllExcept(_) => RowSelection::AllExcept(indices),
+                    _ => RowSelection::Only(indices),
+                }
+            })
+            .into()
+        }
+    };
+
+    let selection_column = selection_column && selection != Selection::None;
+    let col_count = Row::COLUMN_COUNT + usize::from(selection_column);
+
+    let select_row = {
+        let on_selection_change = on_selection_change.clone();
+
+        // `modifiers` are whether the meta and the shift key are pressed. See `get_keyboard_modifiers`.
+        Arc::new(move |i: usize, modifiers: (bool, bool)| {
+            let RowState::Loaded(row) =
+                loaded_rows.with_untracked(|loaded_rows| loaded_rows[i].clone())
+            else {
+                return;
+            };
+
+            update_selection(modifiers, selection, first_selected_index, i, |index| {
+                loaded_row_key(loaded_rows, selection_key_of, index)
+            });
+
+            on_selection_change.run(SelectionChangeEvent {
+                row: row.into(),
+                row_index: i,
+                row_key: key_of(row),
+                selected: selected_indices.with_untracked(|selected| selected.contains(&i)),
+            });
+        })
+    };
+
+    let selection_state = Signal::derive(move || {
+        SelectionState::of_selection(selection, &filter.read(), row_count.get())
+    });
+
+    let toggle_all_selected = {
+        let on_selection_change = on_selection_change.clone();
+
+        Callback::new(move |_: ()| {
+            let selected_before = loaded_rows.with_untracked(|loaded_rows| {
+                selected_indices.with_untracked(|selected| {
+                    loaded_rows
+                        .iter_loaded()
+                        .map(|(i, row)| (i, row, selected.contains(&i)))
+                        .collect::<Vec<_>>()
+                })
+            });
+
+            if selection_state.get_untracked() == SelectionState::All {
+                selection.deselect_all();
+            } else if filter.with_untracked(FilterModel::is_empty) {
+                selection.select_all();
+            } else {
+                // the rows that aren't loaded are selected by the filter they match
+                selection.select_all_matching(filter.get_untracked());
+            }
+            first_selected_index.set(None);
+
+            // notify about every loaded row whose selection changed
+            for (i, row, was_selected) in selected_before {
+                let selected = selected_indices.with_untracked(|selected| selected.contains(&i));
+
+                if selected != was_selected {
+                    on_selection_change.run(SelectionChangeEvent {
+                        row: row.into(),
+                        row_index: i,
+                        row_key: key_of(row),
+                        selected,
+                    });
+                }
+            }
+        })
     };
 
+    provide_context(TableCellContext {
+        locale: use_context::<NumberLocale>().unwrap_or_default(),
+        alignment_class: Callback::new(move |alignment| class_provider.cell_alignment(alignment)),
+    });
+
+    provide_context(SelectionColumnContext {
+        enabled: selection_column,
+        selectable: selection != Selection::None,
+        toggle_row: {
+            let select_row = select_row.clone();
+            Callback::new(move |(i, evt): (usize, web_sys::MouseEvent)| {
+                // toggle the row as if the meta key was pressed unless a range is selected
+                let (meta_pressed, shift_pressed) = get_keyboard_modifiers(&evt);
+                select_row(i, (meta_pressed || !shift_pressed, shift_pressed))
+            })
+        },
+        cell_class: Callback::new(move |(i, selected): (usize, bool)| {
+            class_provider.selection_cell(i, selected)
+        }),
+        checkbox_class: Signal::stored(class_provider.selection_checkbox()),
+    });
+
     let scroll_container = scroll_container.into_element_maybe_signal();
 
     let UseScrollReturn { y, set_y, .. } = use_scroll_with_options(
@@ -396,20 +616,392 @@ where
     let tbody_el = RwSignal::new_local(None::<web_sys::Element>);
 
     let compute_average_row_height = use_debounce_fn(
-        move || {
-            compute_average_row_height_from_loaded(
-                tbody_el,
-                display_range,
-                y,
-                &set_y,
-                set_average_row_height,
-                placeholder_height_before,
-                loaded_rows,
-            );
+        {
+            let set_y = set_y.clone();
+            move || {
+                compute_average_row_height_from_loaded(
+                    tbody_el,
+                    display_range,
+                    y,
+                    &set_y,
+                    set_average_row_height,
+                    placeholder_height_before,
+                    loaded_rows,
+                );
+            }
         },
         50.0,
     );
 
+    // ARIA attributes of the table. The row count includes the head row and the rows that aren't rendered.
+    Effect::new(move || {
+        let Some(table) = tbody_el.get().and_then(|tbody| tbody.parent_element()) else {
+            return;
+        };
+
+        let aria_row_count = row_count
+            .get()
+            .map_or_else(|| "-1".to_string(), |row_count| (row_count + 1).to_string());
+
+        let _ = table.set_attribute("role", "grid");
+        let _ = table.set_attribute("aria-rowcount", &aria_row_count);
+        if matches!(
+            selection,
+            Selection::Multiple(_) | Selection::MultipleByKey { .. }
+        ) {
+            let _ = table.set_attribute("aria-multiselectable", "true");
+        }
+    });
+
+    // Keyboard navigation with a roving tabindex: only the focused cell can be reached with the tab key.
+    let focused_cell = RwSignal::new(None::<CellPosition>);
+    let should_focus = StoredValue::new(false);
+    let tabbable_cell = StoredValue::new_local(None::<web_sys::Element>);
+
+    Effect::new(move || {
+        let position = focused_cell.get().unwrap_or_default();
+        // rows are rendered again when they are loaded or scrolled into view
+        display_range.track();
+        loaded_rows.track();
+
+        // wait until the rows are rendered
+        request_animation_frame(move || {
+            let Some(tbody) = tbody_el.get_untracked() else {
+                return;
+            };
+
+            let cell = find_cell(&tbody, position);
+            let focus = cell.is_some() && should_focus.get_value();
+
+            // if the focused row isn't rendered, the first row can be tabbed to instead
+            let Some(cell) = cell.or_else(|| find_first_cell(&tbody, position.col)) else {
+                return;
+            };
+
+            tabbable_cell.update_value(|tabbable_cell| {
+                set_tabbable_cell(&cell, tabbable_cell.as_ref());
+                *tabbable_cell = Some(cell.clone());
+            });
+
+            if focus {
+                should_focus.set_value(false);
+                focus_cell(&cell);
+            }
+        });
+    });
+
+    let scroll_to_row = move |row_index: usize| match display_strategy {
+        DisplayStrategy::Pagination {
+            controller,
+            row_count,
+        } => {
+            let page = row_index / row_count.max(1);
+            if controller.current_page.get_untracked() != page {
+                controller.current_page.set(page);
+            }
+        }
+        DisplayStrategy::Virtualization | DisplayStrategy::InfiniteScroll => {
+            // rendered rows are scrolled into view when they are focused
+            if !display_range.get_untracked().contains(&row_index) {
+                set_y(row_index as f64 * average_row_height.get_untracked());
+            }
+        }
+    };
+
+    // the cell at a position in a rendered row
+    let cell_index = move |position: CellPosition| {
+        let col = position.col.checked_sub(usize::from(selection_column))?;
+
+        columns
+            .with_untracked(|columns| columns.get(col).copied())
+            .map(|column| CellIndex {
+                row: position.row,
+                column,
+            })
+    };
+
+    // provided to the cells of every row so they can add the classes of the selected cells
+    let cell_selection_context = cell_selection.map(|cell_selection| {
+        let is_selected = Callback::new(move |index: CellIndex<Column>| {
+            cell_selection.with(|cell_selection| {
+                columns.with(|columns| cell_selection.contains(index, columns))
+            })
+        });
+        let selected_class = Signal::stored(class_provider.selected_cell());
+
+        move |row: usize| CellSelectionContext {
+            row,
+            is_selected,
+            selected_class,
+        }
+    });
+
+    if let Some(cell_selection) = cell_selection {
+        let _ = use_event_listener(tbody_el, leptos::ev::click, move |evt| {
+            if let Some(index) = cell_position_of(evt.target()).and_then(cell_index) {
+                update_cell_selection(cell_selection, index, get_keyboard_modifiers(&evt));
+            }
+        });
+    }
+
+    {
+        let cell_text = cell_text.unwrap_or_else(CellTextGetter::from_cell_renderers);
+        let row_reader = row_reader.clone();
+
+        let _ = use_event_listener(tbody_el, leptos::ev::copy, move |evt| {
+            if is_editable_target(evt.target()) {
+                return;
+            }
+
+            // the cells are built in the table to see its contexts like the locale
+            let grid = row_reader.with_table_owner(|| {
+                selected_text_grid(
+                    loaded_rows,
+                    selected_indices,
+                    cell_selection,
+                    columns,
+                    &cell_text,
+                )
+            });
+
+            if grid.rows.is_empty() {
+                return;
+            }
+
+            if let Some(clipboard_data) = evt.clipboard_data() {
+                let _ = clipboard_data.set_data("text/plain", &grid.to_tsv());
+                let _ = clipboard_data.set_data("text/html", &grid.to_html());
+                evt.prevent_default();
+            }
+        });
+    }
+
+    let _ = use_event_listener(tbody_el, leptos::ev::focusin, move |evt| {
+        if let Some(position) = cell_position_of(evt.target())
+            && focused_cell.get_untracked() != Some(position)
+        {
+            focused_cell.set(Some(position));
+        }
+    });
+
+    let _ = use_event_listener(tbody_el, leptos::ev::keydown, {
+        let select_row = select_row.clone();
+
+        move |evt| {
+            if is_editable_target(evt.target()) {
+                return;
+            }
+
+            let current = focused_cell.get_untracked().unwrap_or(CellPosition {
+                row: display_range.get_untracked().start,
+                col: 0,
+            });
+            let meta_pressed = evt.meta_key() || evt.ctrl_key();
+            let shift_pressed = evt.shift_key();
+            let key = evt.key();
+
+            if key == " " {
+                if selection != Selection::None {
+                    evt.prevent_default();
+                    select_row(current.row, (meta_pressed || !shift_pressed, shift_pressed));
+                }
+                return;
+            }
+
+            let total_row_count = row_count
+                .get_untracked()
+                .unwrap_or_else(|| loaded_rows.with_untracked(LoadedRows::len));
+            let cell_count = columns.with_untracked(Vec::len) + usize::from(selection_column);
+            let page_size = match display_strategy {
+                DisplayStrategy::Pagination { row_count, .. } => row_count,
+                DisplayStrategy::Virtualization | DisplayStrategy::InfiniteScroll => {
+                    (height.get_untracked() / average_row_height.get_untracked()).floor() as usize
+                }
+            };
+
+            let Some(next) = next_cell_position(
+                &key,
+                meta_pressed,
+                current,
+                total_row_count,
+                cell_count,
+                page_size,
+            ) else {
+                return;
+            };
+
+            evt.prevent_default();
+
+            if let Some(cell_selection) = cell_selection {
+                if let Some(index) = cell_index(next) {
+                    // the meta key is used by Ctrl+Home/End and doesn't add a range here
+                    update_cell_selection(cell_selection, index, (false, shift_pressed));
+                }
+            } else if shift_pressed
+                && next.row != current.row
+                && matches!(
+                    selection,
+                    Selection::Multiple(_) | Selection::MultipleByKey { .. }
+                )
+            {
+                if first_selected_index.get_untracked().is_none() {
+                    first_selected_index.set(Some(current.row));
+                }
+                select_row(next.row, (false, true));
+            }
+
+            scroll_to_row(next.row);
+            should_focus.set_value(true);
+            focused_cell.set(Some(next));
+        }
+    });
+
+    Effect::new({
+        let loading_queue = Rc::clone(&loading_queue);
+        let invalidated_rows = reload_controller.subscribe_invalidated();
+
+        move || {
+            // triggered when `ReloadController::invalidate_rows()` is called
+            let invalidated = invalidated_rows.take();
+
+            if invalidated.is_empty() {
+                return;
+            }
+
+            let aborted = loading_queue.borrow_mut().abort_overlapping(&invalidated);
+
+            // this triggers the loading effect below which loads the visible rows again
+            loaded_rows.update(|loaded_rows| {
+                for range in aborted {
+                    loaded_rows.write_placeholder(range);
+                }
+                for range in invalidated {
+                    loaded_rows.invalidate(range);
+                }
+            });
+        }
+    });
+
+    let start_loaders = {
+        let rows = Rc::clone(&rows);
+        let loading_queue = Rc::clone(&loading_queue);
+        let set_known_row_count = set_known_row_count.clone();
+
+        move || {
+            let dropped = loading_queue.borrow_mut().drop_unwanted();
+            reset_dropped_chunks(dropped, loaded_rows);
+
+            let available_slots = loading_queue.borrow().available_slots();
+
+            for _ in 0..available_slots {
+                loading_queue.borrow_mut().start_loader();
+
+                spawn_local({
+                    let rows = Rc::clone(&rows);
+                    let loading_queue = Rc::clone(&loading_queue);
+                    let set_known_row_count = set_known_row_count.clone();
+                    let compute_average_row_height = compute_average_row_height.clone();
+
+                    async move {
+                        loop {
+                            let dropped = loading_queue.borrow_mut().drop_unwanted();
+                            reset_dropped_chunks(dropped, loaded_rows);
+
+                            let Some((missing_range, kind)) =
+                                loading_queue.borrow_mut().pop_nearest()
+                            else {
+                                break;
+                            };
+
+                            let (Some(latest_reload_count), Some(row_count_opt)) = (
+                                reload_count.try_get_untracked(),
+                                row_count.try_get_untracked(),
+                            ) else {
+                                break;
+                            };
+
+                            let abort_token = loading_queue
+                                .borrow_mut()
+                                .start_chunk(missing_range.clone());
+
+                            let result = rows
+                                .provider()
+                                .await
+                                .get_rows_with_context(missing_range.clone(), abort_token.clone())
+                                .await
+                                .map_err(|err| format!("{err:?}"));
+
+                            loading_queue.borrow_mut().finish_chunk(&abort_token);
+
+                            let Some(reload_count) = reload_count.try_get_untracked() else {
+                                break;
+                            };
+
+                            // make sure the loaded data is still valid
+                            if reload_count != latest_reload_count || abort_token.is_aborted() {
+                                continue;
+                            }
+
+                            if kind == ChunkKind::Refresh {
+                                // on error the stale rows are kept
+                                if let Ok((new_rows, loaded_range)) = result {
+                                    refresh_rows(loaded_rows, new_rows, loaded_range);
+                                }
+                                continue;
+                            }
+
+                            if let Ok((_, loaded_range)) = &result
+                                && loaded_range.end < missing_range.end
+                            {
+                                match row_count_opt {
+                                    // Use pre-fetched value!
+                                    Some(row_count) => {
+                                        if loaded_range.end < row_count {
+                                            set_known_row_count(loaded_range.end);
+                                        }
+                                    }
+                                    None => {
+                                        set_known_row_count(loaded_range.end);
+                                    }
+                                }
+                            }
+                            loaded_rows.write().write_loaded(result, missing_range);
+                            compute_average_row_height();
+                        }
+
+                        loading_queue.borrow_mut().finish_loader();
+                    }
+                });
+            }
+        }
+    };
+
+    Effect::new({
+        let rows = Rc::clone(&rows);
+        let loading_queue = Rc::clone(&loading_queue);
+        let set_known_row_count = set_known_row_count.clone();
+        let start_loaders = start_loaders.clone();
+
+        move |prev_run: Option<()>| {
+            // triggered when `ReloadController::soft_reload()` is called
+            reload_controller.track_soft_reload();
+
+            if prev_run.is_none() {
+                return;
+            }
+
+            spawn_local(soft_reload(
+                Rc::clone(&rows),
+                Rc::clone(&loading_queue),
+                loaded_rows,
+                display_range.get_untracked(),
+                reload_count,
+                set_known_row_count.clone(),
+                start_loaders.clone(),
+            ));
+        }
+    });
+
     Effect::new(move || {
         // with this a reload triggers this effect
         reload_count.track();
@@ -453,6 +1045,21 @@ where
 
         let range = start..end;
 
+        if let Some(max_loaded_rows) = max_loaded_rows {
+            // also keep the rows that are still displayed until the display range is updated
+            let prev_display_range = display_range.get_untracked();
+            let keep_range =
+                range.start.min(prev_display_range.start)..range.end.max(prev_display_range.end);
+
+            loaded_rows.update_untracked(|loaded_rows| {
+                loaded_rows.evict(keep_range, max_loaded_rows, |index| {
+                    selected_indices.with_untracked(|selected| {
+                        matches!(selected, RowSelection::Only(indices) if indices.contains(&index))
+                    })
+                })
+            });
+        }
+
         set_display_range.set(match display_strategy {
             DisplayStrategy::Virtualization | DisplayStrategy::InfiniteScroll => range.clone(),
             DisplayStrategy::Pagination { row_count, .. } => {
@@ -466,21 +1073,27 @@ where
             }
         });
 
-        let missing_range =
-            loaded_rows.with_untracked(|loaded_rows| loaded_rows.missing_range(range.clone()));
+        let missing_range = loaded_rows
+            .try_update_untracked(|loaded_rows| {
+                loaded_rows.record_access(range.clone());
+                loaded_rows.missing_range(range.clone())
+            })
+            .flatten();
+
+        loading_queue
+            .borrow_mut()
+            .set_ranges(range.clone(), first_visible..first_visible + visible_count);
 
-        if let Some(missing_range) = missing_range {
+        if let Some(missing_range) = missing_range
+            // Don't proceed with empty ranges
+            && !missing_range.is_empty()
+        {
             // Ensure missing_range is valid *after* all calculations
             let missing_start = missing_range.start.min(missing_range.end);
             let missing_end = missing_range.end; // Already correct
 
             let missing_range = missing_start..missing_end;
 
-            if missing_range.is_empty() {
-                // Don't proceed with empty ranges
-                return;
-            }
-
             loaded_rows.write().write_loading(missing_range.clone());
 
             let mut loading_ranges = vec![];
@@ -500,73 +1113,32 @@ where
                 loading_ranges.push(missing_range);
             }
 
-            // TODO : implement max concurrent requests
-            for missing_range in loading_ranges {
-                let compute_average_row_height = compute_average_row_height.clone();
-                spawn_local({
-                    let rows = Rc::clone(&rows);
-                    let set_known_row_count = set_known_row_count.clone();
-
-                    async move {
-                        let Some(latest_reload_count) = reload_count.try_get_untracked() else {
-                            return;
-                        };
-
-                        // TODO: can we avoid this?
-                        let result = rows
-                            .borrow()
-                            .get_rows(missing_range.clone())
-                            .await
-                            .map_err(|err| format!("{err:?}"));
-
-                        if let Some(reload_count) = reload_count.try_get_untracked() {
-                            // make sure the loaded data is still valid
-                            if reload_count != latest_reload_count {
-                                return;
-                            }
-
-                            if let Ok((_, loaded_range)) = &result
-                                && loaded_range.end < missing_range.end
-                            {
-                                match row_count_opt {
-                                    // Use pre-fetched value!
-                                    Some(row_count) => {
-                                        if loaded_range.end < row_count {
-                                            set_known_row_count(loaded_range.end);
-                                        }
-                                    }
-                                    None => {
-                                        set_known_row_count(loaded_range.end);
-                                    }
-                                }
-                            }
-                            loaded_rows.write().write_loaded(result, missing_range);
-                            compute_average_row_height();
-                        }
-                    }
-                });
-            }
+            loading_queue
+                .borrow_mut()
+                .push(loading_ranges, ChunkKind::Load);
         }
-    });
-
-    let thead_content =
-        Row::render_head_row(sorting.into(), on_head_click, drag_handler, columns).into_any();
 
-    fn clamp_range(range: Range<usize>, len: usize) -> Range<usize> {
-        let start = range.start.min(len);
-        let end = range.end.min(len);
+        start_loaders();
+    });
 
-        if start > end {
-            return end..start;
-        }
-        start..end
+    let thead_content = view! {
+        {selection_column.then(|| {
+            selection_head_cell(
+                selection,
+                selection_state,
+                toggle_all_selected,
+                class_provider.selection_head_cell(),
+                Signal::stored(class_provider.selection_checkbox()),
+            )
+        })}
+        {Row::render_head_row(sorting.into(), on_head_click, drag_handler, columns)}
     }
+    .into_any();
 
     let tbody_content = {
         let row_renderer = row_renderer.clone();
         let loading_row_renderer = loading_row_renderer.clone();
         let error_row_renderer = error_row_renderer.clone();
-        let on_selection_change = on_selection_change.clone();
 
         view! {
             {row_placeholder_renderer.run(placeholder_height_before)}
@@ -576,11 +1148,7 @@ where
                     let loaded_rows = loaded_rows.read();
                     let display_range = display_range.read();
 
-                    let iter = loaded_rows[clamp_range(display_range.clone(), loaded_rows.len())]
-                        .iter()
-                        .cloned()
-                        .enumerate()
-                        .map(|(i, row)| (i + display_range.start, row));
+                    let iter = display_range.clone().zip(loaded_rows.rows_in(display_range.clone()));
 
                     if let Some(loading_row_display_limit) = loading_row_display_limit {
                         let mut loading_row_count = 0;
@@ -598,9 +1166,12 @@ where
                     }
                 }
 
-                key=|(idx, row)| {
+                key=move |(idx, row)| {
                     match row {
-                        RowState::Loaded(_) => idx.to_string(),
+                        RowState::Loaded(row) => match key_of(*row) {
+                            Some(key) => format!("key-{key}"),
+                            None => format!("index-{idx}"),
+                        },
                         RowState::Error(_) => format!("error-{idx}"),
                         RowState::Loading | RowState::Placeholder => format!("loading-{idx}"),
                     }
@@ -610,7 +1181,7 @@ where
                     let row_renderer = row_renderer.clone();
                     let loading_row_renderer = loading_row_renderer.clone();
                     let error_row_renderer = error_row_renderer.clone();
-                    let on_selection_change = on_selection_change.clone();
+                    let select_row = select_row.clone();
                     move |(i, row)| {
                         match row {
                             RowState::Loaded(row) => {
@@ -623,30 +1194,45 @@ where
                                         .row(i, selected_signal.get(), row_class.read().as_str())
                                 });
 
-                                let on_select = {
-                                    let on_selection_change = on_selection_change.clone();
+                                if let Some(cell_selection_context) = cell_selection_context {
+                                    provide_context(cell_selection_context(i));
+                                }
 
+                                let on_select = {
+                                    let select_row = select_row.clone();
                                     move |evt: web_sys::MouseEvent| {
-                                        update_selection(evt, selection, first_selected_index, i);
-
-                                        let selection_change_event = SelectionChangeEvent {
-                                            row: row.into(),
-                                            row_index: i,
-                                            selected: selected_signal.get_untracked(),
-                                        };
-
-                                        on_selection_change.run(selection_change_event);
+                                        // a click selects cells instead if they can be selected
+                                        if cell_selection.is_none() {
+                                            select_row(i, get_keyboard_modifiers(&evt))
+                                        }
                                     }
                                 };
 
                                 Effect::watch(
                                     move || { row.track() },
                                     move |_, _, _| {
+                                        let refreshed = loaded_rows
+                                            .try_update_untracked(|loaded_rows| {
+                                                loaded_rows.take_refreshed(row)
+                                            })
+                                            .unwrap_or(true);
+
+                                        // changed by a soft reload and not by an edit
+                                        if refreshed {
+                                            return;
+                                        }
+
+                                        // edited rows are never evicted from the cache
+                                        loaded_rows.update_untracked(|loaded_rows| {
+                                            loaded_rows.mark_dirty(row)
+                                        });
+
                                         let on_change = on_change.get_value();
 
                                         on_change
                                             .run(ChangeEvent {
                                                 row_index: i,
+                                                row_key: key_of(row),
                                                 changed_row: row.into(),
                                             });
                                     },
@@ -656,7 +1242,7 @@ where
                                     .run(class_signal, row, i, selected_signal, on_select.into(), columns)
                             }
                             RowState::Error(err) => {
-                                error_row_renderer.run(err, i, Row::COLUMN_COUNT)
+                                error_row_renderer.run(err, i, col_count)
                             }
                             RowState::Loading | RowState::Placeholder => {
                                 loading_row_renderer
@@ -681,7 +1267,7 @@ where
                                                 )
                                         }),
                                         i,
-                                        Row::COLUMN_COUNT,
+                                        col_count,
                                     )
                             }
                         }
@@ -782,17 +1368,187 @@ fn compute_average_row_height_from_loaded<Row, Column, ClsP>(
     }
 }
 
+/// Reloads the rows in `range` in the background and updates the loaded rows in place.
+/// Rows outside of `range` are discarded so they are loaded again once they become visible.
+///
+/// The chunks are queued in the `loading_queue` like missing rows so they are subject to the same
+/// limit of concurrent requests and are aborted if they are scrolled out of view.
+async fn soft_reload<Row, Column, Err, DataP>(
+    rows: Rc<DataProviderCell<DataP>>,
+    loading_queue: Rc<RefCell<LoadingQueue>>,
+    loaded_rows: RwSignal<LoadedRows<Row>>,
+    range: Range<usize>,
+    reload_count: ReadSignal<usize>,
+    set_known_row_count: impl Fn(usize),
+    start_loaders: impl Fn(),
+) where
+    Row: Send + Sync + 'static,
+    DataP: TableDataProvider<Row, Column, Err> + 'static,
+    Err: Debug,
+{
+    let Some(latest_reload_count) = reload_count.try_get_untracked() else {
+        return;
+    };
+
+    loaded_rows.update(|loaded_rows| loaded_rows.retain_range(range.clone()));
+
+    let row_count = rows.provider().await.row_count().await;
+
+    // a (hard) reload in the meantime makes the result obsolete
+    if reload_count.try_get_untracked() != Some(latest_reload_count) {
+        return;
+    }
+
+    if let Some(row_count) = row_count
+        && loaded_rows.with_untracked(|loaded_rows| loaded_rows.len()) != row_count
+    {
+        set_known_row_count(row_count);
+    }
+
+    let chunks = match DataP::CHUNK_SIZE {
+        Some(chunk_size) => {
+            let start = range.start / chunk_size * chunk_size;
+            (start..range.end)
+                .step_by(chunk_size)
+                .map(|start| start..start + chunk_size)
+                .collect()
+        }
+        None => vec![range],
+    };
+
+    loading_queue.borrow_mut().push(chunks, ChunkKind::Refresh);
+    start_loaders();
+}
+
+/// Writes the rows of a refreshed chunk and updates the signals of the loaded rows in place.
+fn refresh_rows<Row>(
+    loaded_rows: RwSignal<LoadedRows<Row>>,
+    new_rows: Vec<Row>,
+    range: Range<usize>,
+) where
+    Row: Send + Sync + 'static,
+{
+    let updates = loaded_rows
+        .try_update(|loaded_rows| loaded_rows.write_refreshed(new_rows, range))
+        .unwrap_or_default();
+
+    for (row, new_row) in updates {
+        row.set(new_row);
+    }
+}
+
+/// Chunks that were dropped from the loading queue before they were started have to be
+/// reset so they are loaded again once they become visible.
+fn reset_dropped_chunks<Row>(dropped: Vec<Range<usize>>, loaded_rows: RwSignal<LoadedRows<Row>>)
+where
+    Row: Send + Sync + 'static,
+{
+    if dropped.is_empty() {
+        return;
+    }
+
+    loaded_rows.update(|loaded_rows| {
+        for range in dropped {
+            loaded_rows.write_placeholder(range);
+        }
+    });
+}
+
 fn get_keyboard_modifiers(evt: &web_sys::MouseEvent) -> (bool, bool) {
     let meta_pressed = evt.meta_key() || evt.ctrl_key();
     let shift_pressed = evt.shift_key();
     (meta_pressed, shift_pressed)
 }
 
-fn update_selection(
-    evt: web_sys::MouseEvent,
-    selection: Selection,
+fn loaded_row_key<Row: Send + Sync + 'static>(
+    loaded_rows: RwSignal<LoadedRows<Row>>,
+    key_of: impl Fn(RwSignal<Row>) -> Option<String>,
+    index: usize,
+) -> Option<String> {
+    loaded_rows.with_untracked(|loaded_rows| match &loaded_rows[index] {
+        RowState::Loaded(row) => key_of(*row),
+        _ => None,
+    })
+}
+
+/// The texts of the selected cells or, if there are none, of the selected rows.
+/// Rows that aren't loaded are left out or are empty in case of cell ranges.
+fn selected_text_grid<Row, Column>(
+    loaded_rows: RwSignal<LoadedRows<Row>>,
+    selected_indices: Signal<RowSelection<usize>>,
+    cell_selection: Option<RwSignal<CellSelection<Column>>>,
+    columns: RwSignal<Vec<Column>>,
+    cell_text: &CellTextGetter<Row, Column>,
+) -> TextGrid
+where
+    Row: Send + Sync + 'static,
+    Column: Copy + PartialEq + Send + Sync + 'static,
+{
+    loaded_rows.with_untracked(|loaded_rows| {
+        let row_texts =
+            |index: usize, row_cells: &mut RowCells<Row, String>, column_count| match &loaded_rows
+                [index]
+            {
+                RowState::Loaded(row) => row.with_untracked(|row| row_cells(row)),
+                _ => vec![String::new(); column_count],
+            };
+
+        let cell_ranges = cell_selection
+            .map(|cell_selection| cell_selection.get_untracked().ranges)
+            .unwrap_or_default();
+
+        let rows = columns.with_untracked(|columns| {
+            if cell_ranges.is_empty() {
+                let mut row_cells = cell_text.cells(columns);
+
+                selected_indices.with_untracked(|selected| {
+                    loaded_rows
+                        .iter_loaded()
+                        .filter(|(index, _)| selected.contains(index))
+                        .map(|(index, _)| row_texts(index, &mut row_cells, columns.len()))
+                        .collect()
+                })
+            } else {
+                cell_ranges
+                    .iter()
+                    .flat_map(|range| {
+                        let range_columns = range.columns(columns);
+                        let mut row_cells = cell_text.cells(range_columns);
+                        let rows = range.rows();
+                        let rows = *rows.start()..(*rows.end() + 1).min(loaded_rows.len());
+                        rows.map(|index| row_texts(index, &mut row_cells, range_columns.len()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            }
+        });
+
+        TextGrid { rows }
+    })
+}
+
+fn update_cell_selection<Column: Copy + PartialEq + Send + Sync + 'static>(
+    cell_selection: RwSignal<CellSelection<Column>>,
+    index: CellIndex<Column>,
+    (meta_pressed, shift_pressed): (bool, bool),
+) {
+    cell_selection.update(|cell_selection| {
+        if meta_pressed {
+            cell_selection.add(index);
+        } else if shift_pressed {
+            cell_selection.extend(index);
+        } else {
+            cell_selection.select(index);
+        }
+    });
+}
+
+fn update_selection<Row, Column: Send + Sync + 'static>(
+    modifiers: (bool, bool),
+    selection: Selection<Row, Column>,
     first_selected_index: RwSignal<Option<usize>>,
     i: usize,
+    row_key: impl Fn(usize) -> Option<String>,
 ) {
     match selection {
         Selection::None => {}
@@ -804,40 +1560,99 @@ fn update_selection(
             }
         }
         Selection::Multiple(selected_indices) => {
-            let mut indices = selected_indices.write();
-            let (meta_pressed, shift_pressed) = get_keyboard_modifiers(&evt);
-
-            if meta_pressed {
-                if indices.contains(&i) {
-                    indices.remove(&i);
-                } else {
-                    indices.insert(i);
-                }
-                match indices.len() {
-                    0 => first_selected_index.set(None),
-                    1 => {
-                        first_selected_index.set(Some(i));
-                    }
-                    _ => {
-                        // do nothing
-                    }
-                }
-            } else if shift_pressed {
-                if let Some(first_selected_index) = first_selected_index.get() {
-                    let min = first_selected_index.min(i);
-                    let max = first_selected_index.max(i);
-                    for i in min..=max {
-                        indices.insert(i);
-                    }
-                } else {
-                    indices.insert(i);
-                    first_selected_index.set(Some(i));
-                }
-            } else {
-                HashSet::clear(&mut *indices);
-                indices.insert(i);
+            update_multiple_selection(
+                modifiers,
+                &mut selected_indices.write(),
+                first_selected_index,
+                i,
+                Some,
+            );
+        }
+        Selection::MultipleByKey {
+            keys: selected_keys,
+            ..
+        } => {
+            update_multiple_selection(
+                modifiers,
+                &mut selected_keys.write(),
+                first_selected_index,
+                i,
+                row_key,
+            );
+        }
+    }
+}
+
+/// Updates a multiple selection that contains the keys of the rows.
+/// `key` returns the key of the row at an index or `None` if it can't be selected.
+/// `modifiers` are whether the meta and the shift key are pressed.
+fn update_multiple_selection<K: Eq + std::hash::Hash, Column>(
+    (meta_pressed, shift_pressed): (bool, bool),
+    keys: &mut RowSelection<K, Column>,
+    first_selected_index: RwSignal<Option<usize>>,
+    i: usize,
+    key: impl Fn(usize) -> Option<K>,
+) {
+    let Some(clicked_key) = key(i) else {
+        return;
+    };
+
+    if meta_pressed {
+        keys.toggle(clicked_key);
+        match keys {
+            RowSelection::Only(keys) if keys.is_empty() => first_selected_index.set(None),
+            RowSelection::Only(keys) if keys.len() == 1 => {
                 first_selected_index.set(Some(i));
             }
+            _ => {
+                // do nothing
+            }
+        }
+    } else if shift_pressed {
+        if let Some(first_selected_index) = first_selected_index.get() {
+            let min = first_selected_index.min(i);
+            let max = first_selected_index.max(i);
+            keys.extend((min..=max).filter_map(key));
+        } else {
+            keys.insert(clicked_key);
+            first_selected_index.set(Some(i));
         }
+    } else {
+        keys.deselect_all();
+        keys.insert(clicked_key);
+        first_selected_index.set(Some(i));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clicks_update_a_multiple_selection() {
+        let owner = Owner::new();
+        owner.set();
+
+        let selected = RwSignal::new(RowSelection::default());
+        let selection = Selection::<usize>::Multiple(selected);
+        let first_selected_index = RwSignal::new(None);
+        let click = |i, modifiers| {
+            update_selection(modifiers, selection, first_selected_index, i, |_| None)
+        };
+
+        click(2, (false, false));
+        click(5, (false, true));
+        assert_eq!(*selected.read(), RowSelection::Only([2, 3, 4, 5].into()));
+
+        click(3, (true, false));
+        assert_eq!(*selected.read(), RowSelection::Only([2, 4, 5].into()));
+
+        // ctrl-click deselects a row of a symbolic selection without loading the others
+        selection.select_all();
+        click(4, (true, false));
+        assert_eq!(*selected.read(), RowSelection::AllExcept([4].into()));
+
+        click(7, (false, false));
+        assert_eq!(*selected.read(), RowSelection::Only([7].into()));
     }
 }
@@ -8,11 +8,20 @@ wrapper_render_fn!(
     thead,
 );
 
-wrapper_render_fn!(
-    /// thead row
-    DefaultTableHeadRowRenderer,
-    tr,
-);
+/// Default thead row renderer. Please note that this is **NOT** a `#[component]`.
+///
+/// # Arguments
+///
+/// * `content` - The content of the renderer. It's like the children of this view.
+/// * `class` - The class attribute that is passed to the root element
+#[allow(non_snake_case)]
+pub fn DefaultTableHeadRowRenderer(content: AnyView, class: Signal<String>) -> impl IntoView {
+    view! {
+        <tr class=class aria-rowindex="1">
+            {content}
+        </tr>
+    }
+}
 
 /// The default table header renderer. Renders roughly
 /// ```html
@@ -59,6 +68,7 @@ where
         <th
             class=move || format!("{} {}", class.get(), drag_classes.get())
             style=style
+            aria-sort=move || sort_direction.get().as_aria_sort()
             draggable="true"
             on:click=move |mouse_event| on_click(TableHeadEvent {
                 index,
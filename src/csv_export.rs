@@ -0,0 +1,541 @@
+use crate::clipboard::write_delimited_row;
+use crate::{AbortToken, CellTextGetter, RowReader, TableRow};
+use leptos::prelude::GetUntracked;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// The number of rows requested at once if the data provider doesn't specify a `CHUNK_SIZE`.
+const DEFAULT_CHUNK_SIZE: usize = 100;
+
+/// Exports all rows of a table as CSV according to RFC 4180.
+///
+/// The rows are requested from the data provider of the table through its [`RowReader`], so they are
+/// sorted and filtered exactly like the table. They are requested chunk by chunk
+/// (see [`TableDataProvider::CHUNK_SIZE`](crate::TableDataProvider::CHUNK_SIZE)) so even large
+/// tables can be exported without loading everything at once. Between chunks the browser gets a
+/// chance to render, so the UI stays responsive. The progress is reported after every chunk and the
+/// export can be cancelled with an [`AbortToken`].
+///
+/// By default the columns that the table shows are exported (see [`RowReader::columns`]) and the text of a
+/// cell is the value formatted like in the table (see [`CellTextGetter::from_cell_renderers`]).
+///
+/// If the sorting or the filter of the table changes during the export, it's stopped with
+/// [`ExportError::TableChanged`], because the remaining rows would be in a different order.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos::prelude::*;
+/// #[derive(TableRow, Clone)]
+/// #[table]
+/// struct Book {
+///     title: String,
+///     #[table(format(precision = 1usize))]
+///     rating: f64,
+/// }
+///
+/// async fn export(row_reader: RowReader<Book>) -> String {
+///     let mut exporter = CsvExporter::new();
+///
+///     exporter
+///         .header(vec!["Title".to_string(), "Rating".to_string()])
+///         .on_progress(|progress| leptos::logging::log!("{} rows exported", progress.exported_rows));
+///
+///     exporter.export_to_string(&row_reader).await.unwrap()
+/// }
+/// ```
+pub struct CsvExporter<Row, Column> {
+    columns: Option<Vec<Column>>,
+    cell_text: CellTextGetter<Row, Column>,
+    header: Option<Vec<String>>,
+    options: ExportOptions,
+}
+
+impl<Row, Column> Default for CsvExporter<Row, Column>
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Row, Column> CsvExporter<Row, Column>
+where
+    Row: Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    /// Exports the columns of the table with the texts of their cell renderers.
+    pub fn new() -> Self
+    where
+        Row: TableRow<Column> + Clone,
+    {
+        Self::with_cell_text(CellTextGetter::from_cell_renderers())
+    }
+
+    /// Exports the columns of the table. `cell_text` returns the formatted text of a cell.
+    /// See [`CellTextGetter`].
+    pub fn with_cell_text(cell_text: impl Into<CellTextGetter<Row, Column>>) -> Self {
+        Self {
+            columns: None,
+            cell_text: cell_text.into(),
+            header: None,
+            options: ExportOptions::default(),
+        }
+    }
+
+    /// Exports only the given `columns` in this order instead of the columns of the table.
+    pub fn columns(&mut self, columns: Vec<Column>) -> &mut Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Writes a first line with these column titles.
+    pub fn header(&mut self, titles: Vec<String>) -> &mut Self {
+        self.header = Some(titles);
+        self
+    }
+
+    /// Call [`AbortToken::abort`] on this token to cancel the export. It's also passed to
+    /// [`TableDataProvider::get_rows_with_context`](crate::TableDataProvider::get_rows_with_context).
+    pub fn abort_token(&mut self, abort_token: AbortToken) -> &mut Self {
+        self.options.abort_token = abort_token;
+        self
+    }
+
+    /// Called after every chunk of rows has been written.
+    pub fn on_progress(&mut self, on_progress: impl Fn(ExportProgress) + 'static) -> &mut Self {
+        self.options.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Requests all rows of the table of `rows` and writes them to `writer`.
+    /// Returns the number of exported rows.
+    pub async fn export<W: Write>(
+        &self,
+        rows: &RowReader<Row, Column>,
+        mut writer: W,
+    ) -> Result<usize, ExportError> {
+        let mut line = String::new();
+
+        if let Some(header) = &self.header {
+            write_delimited_row(&mut line, header, ',');
+            writer.write_all(line.as_bytes())?;
+        }
+
+        let columns = export_columns(&self.columns, rows).unwrap_or_default();
+        let mut row_cells = self.cell_text.cells(&columns);
+
+        let exported_rows = export_rows(rows, &self.options, |row: &Row| {
+            let cells = rows.with_table_owner(|| row_cells(row));
+
+            line.clear();
+            write_delimited_row(&mut line, &cells, ',');
+            writer.write_all(line.as_bytes())?;
+
+            Ok(())
+        })
+        .await?;
+
+        writer.flush()?;
+
+        Ok(exported_rows)
+    }
+
+    /// Same as [`Self::export`] but returns the CSV as a `String`.
+    pub async fn export_to_string(
+        &self,
+        rows: &RowReader<Row, Column>,
+    ) -> Result<String, ExportError> {
+        let mut csv = vec![];
+        self.export(rows, &mut csv).await?;
+
+        // only valid strings are written
+        Ok(String::from_utf8(csv).unwrap_or_default())
+    }
+}
+
+/// The abort token and progress callback that are shared by the exporters.
+pub(crate) struct ExportOptions {
+    pub abort_token: AbortToken,
+    pub on_progress: Option<Box<dyn Fn(ExportProgress)>>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            abort_token: AbortToken::new(),
+            on_progress: None,
+        }
+    }
+}
+
+/// The columns given to an exporter or else the ones of the table. `None` if the table hasn't been rendered.
+pub(crate) fn export_columns<Row, Column>(
+    columns: &Option<Vec<Column>>,
+    rows: &RowReader<Row, Column>,
+) -> Option<Vec<Column>>
+where
+    Row: Send + Sync + 'static,
+    Column: Clone + Send + Sync + 'static,
+{
+    columns
+        .clone()
+        .or_else(|| rows.columns().map(|columns| columns.get_untracked()))
+}
+
+/// Requests all rows of the table of `rows` chunk by chunk and calls `write_row` for each of them.
+/// Returns the number of exported rows.
+pub(crate) async fn export_rows<Row, Column>(
+    rows: &RowReader<Row, Column>,
+    options: &ExportOptions,
+    mut write_row: impl FnMut(&Row) -> Result<(), ExportError>,
+) -> Result<usize, ExportError>
+where
+    Row: Send + Sync + 'static,
+    Column: Send + Sync + 'static,
+{
+    let chunk_size = rows.chunk_size().unwrap_or(DEFAULT_CHUNK_SIZE).max(1);
+
+    // aborted by the abort token of the export or when the sorting or filter of the table changes
+    let abort_token = AbortToken::new();
+    options.abort_token.on_abort({
+        let abort_token = abort_token.clone();
+        move || abort_token.abort()
+    });
+    let _running_export = rows.abort_on_change(&abort_token);
+    let aborted = || {
+        if options.abort_token.is_aborted() {
+            ExportError::Aborted
+        } else {
+            ExportError::TableChanged
+        }
+    };
+
+    let total_rows = rows.row_count().await;
+
+    let mut exported_rows = 0;
+
+    loop {
+        if abort_token.is_aborted() {
+            return Err(aborted());
+        }
+
+        let mut end = exported_rows + chunk_size;
+        if let Some(total_rows) = total_rows {
+            end = end.min(total_rows);
+        }
+        if end <= exported_rows {
+            break;
+        }
+
+        let (chunk, range) = rows
+            .get_rows(exported_rows..end, abort_token.clone())
+            .await
+            .map_err(ExportError::Provider)?;
+
+        if abort_token.is_aborted() {
+            return Err(aborted());
+        }
+
+        for row in &chunk {
+            write_row(row)?;
+        }
+
+        exported_rows += chunk.len();
+
+        if let Some(on_progress) = &options.on_progress {
+            on_progress(ExportProgress {
+                exported_rows,
+                total_rows,
+            });
+        }
+
+        // fewer rows than requested means the end is reached
+        if chunk.is_empty() || range.end < end {
+            break;
+        }
+
+        yield_to_event_loop().await;
+    }
+
+    Ok(exported_rows)
+}
+
+/// The progress of an export that is passed to [`CsvExporter::on_progress`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExportProgress {
+    /// The number of rows that have been exported so far.
+    pub exported_rows: usize,
+    /// The total number of rows if [`TableDataProvider::row_count`](crate::TableDataProvider::row_count)
+    /// returns it.
+    pub total_rows: Option<usize>,
+}
+
+impl ExportProgress {
+    /// The exported fraction of all rows between `0.0` and `1.0` if the total number of rows is known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total_rows.map(|total_rows| {
+            if total_rows == 0 {
+                1.0
+            } else {
+                self.exported_rows as f64 / total_rows as f64
+            }
+        })
+    }
+}
+
+/// Error returned by [`CsvExporter::export`] and the exporter of the `xlsx` feature.
+#[derive(Debug)]
+pub enum ExportError {
+    /// The data provider returned an error. It's formatted with `Debug`.
+    Provider(String),
+    /// Writing the output failed.
+    Io(std::io::Error),
+    /// Writing the Excel file failed.
+    #[cfg(feature = "xlsx")]
+    Xlsx(::rust_xlsxwriter::XlsxError),
+    /// The export was cancelled with the [`AbortToken`].
+    Aborted,
+    /// The sorting or the filter of the table changed during the export. The rows that have been
+    /// written so far are in the previous order, so the export has to be started again.
+    TableChanged,
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Provider(err) => write!(f, "failed to load rows: {err}"),
+            ExportError::Io(err) => write!(f, "failed to write the export: {err}"),
+            #[cfg(feature = "xlsx")]
+            ExportError::Xlsx(err) => write!(f, "failed to write the Excel file: {err}"),
+            ExportError::Aborted => write!(f, "the export was cancelled"),
+            ExportError::TableChanged => {
+                write!(
+                    f,
+                    "the sorting or filter of the table changed during the export"
+                )
+            }
+        }
+    }
+}
+
+/// Lets the browser render and handle events before the next chunk is exported.
+#[cfg(target_arch = "wasm32")]
+async fn yield_to_event_loop() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::task::{Poll, Waker};
+
+    let state = Rc::new(RefCell::new((false, None::<Waker>)));
+
+    leptos::prelude::set_timeout(
+        {
+            let state = Rc::clone(&state);
+            move || {
+                let mut state = state.borrow_mut();
+                state.0 = true;
+                if let Some(waker) = state.1.take() {
+                    waker.wake();
+                }
+            }
+        },
+        std::time::Duration::ZERO,
+    );
+
+    std::future::poll_fn(|cx| {
+        let mut state = state.borrow_mut();
+        if state.0 {
+            Poll::Ready(())
+        } else {
+            state.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn yield_to_event_loop() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_provider_cell::DataProviderCell;
+    use crate::{ColumnSort, TableDataProvider};
+    use leptos::prelude::RwSignal;
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::ops::Range;
+    use std::pin::pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll, Waker};
+
+    struct Numbers {
+        len: usize,
+        descending: bool,
+    }
+
+    impl TableDataProvider<usize, usize> for Numbers {
+        const CHUNK_SIZE: Option<usize> = Some(3);
+
+        async fn get_rows(
+            &self,
+            range: Range<usize>,
+        ) -> Result<(Vec<usize>, Range<usize>), String> {
+            let range = range.start.min(self.len)..range.end.min(self.len);
+            let rows = range
+                .clone()
+                .map(|i| if self.descending { self.len - 1 - i } else { i })
+                .collect();
+            Ok((rows, range))
+        }
+
+        fn set_sorting(&mut self, sorting: &VecDeque<(usize, ColumnSort)>) {
+            self.descending = sorting
+                .front()
+                .is_some_and(|(_, sort)| *sort == ColumnSort::Descending);
+        }
+    }
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!(),
+        }
+    }
+
+    /// Connected like a table that shows the columns in the order `1, 0`.
+    fn row_reader(provider: &Rc<DataProviderCell<Numbers>>) -> RowReader<usize> {
+        let row_reader = RowReader::default();
+        row_reader.connect(provider, RwSignal::new(vec![1, 0]));
+        row_reader
+    }
+
+    fn exporter() -> CsvExporter<usize, usize> {
+        CsvExporter::with_cell_text(|row: &usize, column: usize| {
+            if column == 0 {
+                row.to_string()
+            } else {
+                format!("\"{row}\"")
+            }
+        })
+    }
+
+    #[test]
+    fn exports_all_chunks_in_the_sorting_of_the_table() {
+        let provider = DataProviderCell::new(Numbers {
+            len: 7,
+            descending: false,
+        });
+        provider.mutate(|provider| {
+            provider.set_sorting(&VecDeque::from([(0, ColumnSort::Descending)]))
+        });
+        let progress = Rc::new(Cell::new(0));
+
+        let mut exporter = exporter();
+        exporter
+            .header(vec!["quoted".to_string(), "plain".to_string()])
+            .on_progress({
+                let progress = Rc::clone(&progress);
+                move |p| progress.set(p.exported_rows)
+            });
+
+        let csv = block_on(exporter.export_to_string(&row_reader(&provider))).unwrap();
+
+        assert_eq!(
+            csv,
+            "quoted,plain\r\n\
+             \"\"\"6\"\"\",6\r\n\"\"\"5\"\"\",5\r\n\"\"\"4\"\"\",4\r\n\"\"\"3\"\"\",3\r\n\
+             \"\"\"2\"\"\",2\r\n\"\"\"1\"\"\",1\r\n\"\"\"0\"\"\",0\r\n"
+        );
+        assert_eq!(progress.get(), 7);
+    }
+
+    #[test]
+    fn aborted_export_returns_error() {
+        let provider = DataProviderCell::new(Numbers {
+            len: 7,
+            descending: false,
+        });
+        let abort_token = AbortToken::new();
+
+        let mut exporter = exporter();
+        exporter.abort_token(abort_token.clone()).on_progress({
+            let abort_token = abort_token.clone();
+            move |_| abort_token.abort()
+        });
+
+        let mut csv = vec![];
+        let result = block_on(exporter.export(&row_reader(&provider), &mut csv));
+
+        assert!(matches!(result, Err(ExportError::Aborted)));
+        assert_eq!(
+            csv,
+            b"\"\"\"0\"\"\",0\r\n\"\"\"1\"\"\",1\r\n\"\"\"2\"\"\",2\r\n"
+        );
+    }
+
+    #[test]
+    fn given_columns_replace_the_ones_of_the_table() {
+        let provider = DataProviderCell::new(Numbers {
+            len: 2,
+            descending: false,
+        });
+
+        let mut exporter = exporter();
+        exporter.columns(vec![0]);
+
+        let csv = block_on(exporter.export_to_string(&row_reader(&provider))).unwrap();
+
+        assert_eq!(csv, "0\r\n1\r\n");
+    }
+
+    #[test]
+    fn sorting_change_stops_the_export() {
+        let provider = DataProviderCell::new(Numbers {
+            len: 7,
+            descending: false,
+        });
+        let row_reader = row_reader(&provider);
+
+        let mut exporter = exporter();
+        exporter.on_progress({
+            let provider = Rc::clone(&provider);
+            let row_reader = row_reader.clone();
+            // what the table does when its sorting changes
+            move |_| {
+                provider.mutate(|provider| {
+                    provider.set_sorting(&VecDeque::from([(0, ColumnSort::Descending)]))
+                });
+                row_reader.sorting_or_filter_changed();
+            }
+        });
+
+        let mut csv = vec![];
+        let result = block_on(exporter.export(&row_reader, &mut csv));
+
+        assert!(matches!(result, Err(ExportError::TableChanged)));
+        assert_eq!(
+            csv,
+            b"\"\"\"0\"\"\",0\r\n\"\"\"1\"\"\",1\r\n\"\"\"2\"\"\",2\r\n"
+        );
+        assert!(row_reader.running_exports.borrow().is_empty());
+    }
+
+    #[test]
+    fn unconnected_row_reader_returns_provider_error() {
+        let row_reader = RowReader::<usize>::default();
+
+        let result = block_on(exporter().export_to_string(&row_reader));
+
+        assert!(matches!(result, Err(ExportError::Provider(_))));
+    }
+}
@@ -0,0 +1,313 @@
+#![allow(async_fn_in_trait)]
+
+use crate::{AbortToken, ColumnSort, FilterModel, PaginatedTableDataProvider};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A data source that is paginated by cursors (aka keyset pagination) instead of offsets.
+/// Each request returns the rows and a cursor that is used to request the next rows.
+///
+/// Wrap it in a [`CursorDataProvider`] to pass it to [`TableContent`]. That keeps track of the
+/// cursor of every chunk of rows so the table doesn't have to know any offsets.
+pub trait CursorTableDataProvider<Row, Column, Err: Debug = String> {
+    /// The cursor that points to the next rows. For example the sort key and id of the last row.
+    type Cursor: Clone;
+
+    /// How many rows are returned by every call to [`get_next`](CursorTableDataProvider::get_next).
+    const PAGE_ROW_COUNT: usize;
+
+    /// Same as [`TableDataProvider::MAX_CONCURRENT_REQUESTS`]. Defaults to `Some(1)` because
+    /// the rows can only be requested one after the other anyways.
+    const MAX_CONCURRENT_REQUESTS: Option<usize> = Some(1);
+
+    /// Get the rows that follow `cursor` and the cursor for the rows after those.
+    /// `cursor` is `None` for the first rows.
+    ///
+    /// Return exactly `PAGE_ROW_COUNT` rows unless the end of the data is reached. Return `None`
+    /// as the next cursor once there are no more rows.
+    async fn get_next(
+        &self,
+        cursor: Option<Self::Cursor>,
+    ) -> Result<(Vec<Row>, Option<Self::Cursor>), Err>;
+
+    /// Same as [`get_next`](CursorTableDataProvider::get_next) but also receives an [`AbortToken`].
+    /// See [`TableDataProvider::get_rows_with_context`] for details.
+    #[allow(unused_variables)]
+    async fn get_next_with_context(
+        &self,
+        cursor: Option<Self::Cursor>,
+        abort_token: AbortToken,
+    ) -> Result<(Vec<Row>, Option<Self::Cursor>), Err> {
+        self.get_next(cursor).await
+    }
+
+    /// Same as [`TableDataProvider::row_count`]
+    async fn row_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Same as [`TableDataProvider::set_sorting`]. All cursors are discarded afterwards.
+    #[allow(unused_variables)]
+    fn set_sorting(&mut self, sorting: &VecDeque<(Column, ColumnSort)>) {
+        // by default do nothing
+    }
+
+    /// Same as [`TableDataProvider::set_filter`]. All cursors are discarded afterwards.
+    #[allow(unused_variables)]
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        // by default do nothing
+    }
+
+    /// Same as [`TableDataProvider::track`]. All cursors are discarded whenever one of the
+    /// tracked signals changes.
+    fn track(&self) {
+        // by default do nothing
+    }
+}
+
+/// Turns a [`CursorTableDataProvider`] into a [`TableDataProvider`] that can be passed to [`TableContent`].
+///
+/// It implements [`PaginatedTableDataProvider`] and remembers the cursor of every page that has been
+/// loaded. If a page is requested whose cursor isn't known yet, the pages before it are loaded first.
+/// The cursors are discarded when the sorting or the filter changes or when a tracked signal changes.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// #
+/// # #[derive(Clone)]
+/// # struct Customer { id: u64 }
+/// #
+/// struct CustomerProvider;
+///
+/// impl CursorTableDataProvider<Customer, usize> for CustomerProvider {
+///     // id of the last loaded customer
+///     type Cursor = u64;
+///
+///     const PAGE_ROW_COUNT: usize = 50;
+///
+///     async fn get_next(&self, cursor: Option<u64>) -> Result<(Vec<Customer>, Option<u64>), String> {
+///         // SELECT * FROM customers WHERE id > $cursor ORDER BY id LIMIT 50
+///         # let rows: Vec<Customer> = vec![];
+///         let next_cursor = (rows.len() == Self::PAGE_ROW_COUNT)
+///             .then(|| rows.last().map(|row| row.id))
+///             .flatten();
+///
+///         Ok((rows, next_cursor))
+///     }
+/// }
+///
+/// let rows = CursorDataProvider::<_, Customer, usize>::new(CustomerProvider);
+/// ```
+pub struct CursorDataProvider<D, Row, Column, Err = String>
+where
+    D: CursorTableDataProvider<Row, Column, Err>,
+    Err: Debug,
+{
+    provider: D,
+    /// `cursors[i]` is the cursor to load the page `i + 1`. The first page needs no cursor.
+    cursors: RefCell<Vec<D::Cursor>>,
+    /// Incremented every time the cursors are discarded so running requests don't store stale cursors.
+    generation: Cell<usize>,
+    _marker: PhantomData<(Row, Column, Err)>,
+}
+
+impl<D, Row, Column, Err> CursorDataProvider<D, Row, Column, Err>
+where
+    D: CursorTableDataProvider<Row, Column, Err>,
+    Err: Debug,
+{
+    pub fn new(provider: D) -> Self {
+        Self {
+            provider,
+            cursors: RefCell::new(vec![]),
+            generation: Cell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The wrapped cursor data provider.
+    pub fn provider(&self) -> &D {
+        &self.provider
+    }
+
+    /// Discards all cursors. Afterwards rows are loaded from the beginning again.
+    pub fn reset_cursors(&self) {
+        self.cursors.borrow_mut().clear();
+        self.generation.set(self.generation.get() + 1);
+    }
+}
+
+impl<D, Row, Column, Err> PaginatedTableDataProvider<Row, Column, Err>
+    for CursorDataProvider<D, Row, Column, Err>
+where
+    D: CursorTableDataProvider<Row, Column, Err>,
+    Err: Debug,
+{
+    const PAGE_ROW_COUNT: usize = D::PAGE_ROW_COUNT;
+
+    const MAX_CONCURRENT_REQUESTS: Option<usize> = D::MAX_CONCURRENT_REQUESTS;
+
+    async fn get_page(&self, page_index: usize) -> Result<Vec<Row>, Err> {
+        self.get_page_with_context(page_index, AbortToken::new())
+            .await
+    }
+
+    async fn get_page_with_context(
+        &self,
+        page_index: usize,
+        abort_token: AbortToken,
+    ) -> Result<Vec<Row>, Err> {
+        let generation = self.generation.get();
+
+        loop {
+            // Load the requested page if its cursor is known. Otherwise load the first page
+            // whose cursor isn't known yet to get closer.
+            let (index, cursor) = {
+                let cursors = self.cursors.borrow();
+                let index = page_index.min(cursors.len());
+                let cursor = index.checked_sub(1).map(|i| cursors[i].clone());
+                (index, cursor)
+            };
+
+            let (rows, next_cursor) = self
+                .provider
+                .get_next_with_context(cursor, abort_token.clone())
+                .await?;
+
+            let is_end = next_cursor.is_none();
+
+            if let Some(next_cursor) = next_cursor {
+                let mut cursors = self.cursors.borrow_mut();
+                if self.generation.get() == generation && cursors.len() == index {
+                    cursors.push(next_cursor);
+                }
+            }
+
+            if index == page_index {
+                return Ok(rows);
+            }
+
+            if is_end || abort_token.is_aborted() || self.generation.get() != generation {
+                // the requested page is past the end of the data or not needed anymore
+                return Ok(vec![]);
+            }
+        }
+    }
+
+    async fn row_count(&self) -> Option<usize> {
+        CursorTableDataProvider::<Row, Column, Err>::row_count(&self.provider).await
+    }
+
+    fn set_sorting(&mut self, sorting: &VecDeque<(Column, ColumnSort)>) {
+        self.provider.set_sorting(sorting);
+        self.reset_cursors();
+    }
+
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        self.provider.set_filter(filter);
+        self.reset_cursors();
+    }
+
+    fn track(&self) {
+        self.provider.track();
+        // this is called again every time one of the tracked signals changes
+        self.reset_cursors();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TableDataProvider;
+    use std::future::Future;
+    use std::ops::Range;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Rows are the numbers `0..len`, the cursor is the next number.
+    struct Numbers {
+        len: usize,
+        requested_cursors: RefCell<Vec<Option<usize>>>,
+    }
+
+    impl CursorTableDataProvider<usize, usize> for Numbers {
+        type Cursor = usize;
+
+        const PAGE_ROW_COUNT: usize = 10;
+
+        async fn get_next(
+            &self,
+            cursor: Option<usize>,
+        ) -> Result<(Vec<usize>, Option<usize>), String> {
+            self.requested_cursors.borrow_mut().push(cursor);
+
+            let start = cursor.unwrap_or(0);
+            let end = (start + Self::PAGE_ROW_COUNT).min(self.len);
+
+            Ok(((start..end).collect(), (end < self.len).then_some(end)))
+        }
+    }
+
+    fn numbers(len: usize) -> CursorDataProvider<Numbers, usize, usize> {
+        CursorDataProvider::new(Numbers {
+            len,
+            requested_cursors: RefCell::new(vec![]),
+        })
+    }
+
+    fn get_rows(
+        provider: &CursorDataProvider<Numbers, usize, usize>,
+        range: Range<usize>,
+    ) -> (Vec<usize>, Range<usize>) {
+        let future = pin!(TableDataProvider::get_rows(provider, range));
+        match future.poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result.unwrap(),
+            Poll::Pending => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn chunks_are_loaded_with_stored_cursors() {
+        let provider = numbers(100);
+
+        assert_eq!(get_rows(&provider, 0..10), ((0..10).collect(), 0..10));
+        assert_eq!(get_rows(&provider, 10..20), ((10..20).collect(), 10..20));
+
+        assert_eq!(
+            *provider.provider().requested_cursors.borrow(),
+            vec![None, Some(10)]
+        );
+    }
+
+    #[test]
+    fn missing_cursors_are_loaded_first() {
+        let provider = numbers(100);
+
+        assert_eq!(get_rows(&provider, 30..40), ((30..40).collect(), 30..40));
+        assert_eq!(get_rows(&provider, 20..30), ((20..30).collect(), 20..30));
+
+        assert_eq!(
+            *provider.provider().requested_cursors.borrow(),
+            vec![None, Some(10), Some(20), Some(30), Some(20)]
+        );
+    }
+
+    #[test]
+    fn end_of_data_and_reset() {
+        let mut provider = numbers(25);
+
+        assert_eq!(get_rows(&provider, 20..30), ((20..25).collect(), 20..25));
+        assert_eq!(get_rows(&provider, 30..40), (vec![], 30..30));
+
+        TableDataProvider::set_sorting(&mut provider, &VecDeque::new());
+        provider.provider().requested_cursors.borrow_mut().clear();
+
+        assert_eq!(get_rows(&provider, 10..20), ((10..20).collect(), 10..20));
+        assert_eq!(
+            *provider.provider().requested_cursors.borrow(),
+            vec![None, Some(10)]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(async_fn_in_trait)]
 
-use crate::ColumnSort;
+use crate::{AbortToken, ColumnSort, FilterModel};
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::ops::Range;
@@ -23,6 +23,12 @@ pub trait TableDataProvider<Row, Column, Err: Debug = String> {
     /// instead of this trait.
     const CHUNK_SIZE: Option<usize> = None;
 
+    /// If Some(...), at most this many calls to [`get_rows`](TableDataProvider::get_rows) are
+    /// running at the same time. The remaining chunks are queued and loaded nearest to the
+    /// visible rows first. Queued chunks that are scrolled out of view before they started
+    /// loading are dropped. Defaults to `None` which means unlimited.
+    const MAX_CONCURRENT_REQUESTS: Option<usize> = None;
+
     /// Get all data rows for the table specified by the range. This method is called when the table is rendered.
     /// The range is determined by the visible rows and used to virtualize the table.
     /// The parameter `range` is only determined by visibility and may be out of bounds. It is the
@@ -39,6 +45,22 @@ pub trait TableDataProvider<Row, Column, Err: Debug = String> {
     /// in place of the failed rows.
     async fn get_rows(&self, range: Range<usize>) -> Result<(Vec<Row>, Range<usize>), Err>;
 
+    /// Same as [`get_rows`](TableDataProvider::get_rows) but also receives an [`AbortToken`]
+    /// which is aborted once the result isn't needed anymore. That happens when the sorting changes,
+    /// [`ReloadController::reload`] is called or the range is scrolled out of view before it was loaded.
+    ///
+    /// Override this instead of `get_rows` if your requests are expensive and can be cancelled.
+    /// The result of an aborted request is ignored by [`TableContent`] so it doesn't matter what you return.
+    /// The default implementation ignores the token and calls [`get_rows`](TableDataProvider::get_rows).
+    #[allow(unused_variables)]
+    async fn get_rows_with_context(
+        &self,
+        range: Range<usize>,
+        abort_token: AbortToken,
+    ) -> Result<(Vec<Row>, Range<usize>), Err> {
+        self.get_rows(range).await
+    }
+
     /// The total number of rows in the table. Returns `None` if unknown (which is the default).
     async fn row_count(&self) -> Option<usize> {
         None
@@ -50,11 +72,23 @@ pub trait TableDataProvider<Row, Column, Err: Debug = String> {
     /// For example: `[(0, ColumnSort::Ascending), (1, ColumnSort::Descending)]`
     /// will sort by name first and then by age.
     /// Please note that after calling this method, data will be reloaded through [`get_rows`](TableDataProvider::get_rows).
+    /// This is never called while a request is still running. Instead it is called as soon as all
+    /// running requests are done and before the next one starts.
     #[allow(unused_variables)]
     fn set_sorting(&mut self, sorting: &VecDeque<(Column, ColumnSort)>) {
         // by default do nothing
     }
 
+    /// Set the filter of the table. This is called every time the [`FilterModel`] given to the
+    /// `filter` prop of [`TableContent`] changes. The default implementation does nothing.
+    /// Please note that after calling this method, the row count and the data will be reloaded
+    /// through [`row_count`](TableDataProvider::row_count) and [`get_rows`](TableDataProvider::get_rows).
+    /// Like [`set_sorting`](TableDataProvider::set_sorting) this is never called while a request is still running.
+    #[allow(unused_variables)]
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        // by default do nothing
+    }
+
     /// Call `.track()` in this method on all signals that loading data relies on.
     /// For example a search of filters. Please check the [paginated_rest_datasource example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/paginated_rest_datasource/src/data_provider.rs)
     fn track(&self) {
@@ -75,12 +109,26 @@ pub trait PaginatedTableDataProvider<Row, Column, Err: Debug = String> {
     /// How many rows per page
     const PAGE_ROW_COUNT: usize;
 
+    /// Same as [`TableDataProvider::MAX_CONCURRENT_REQUESTS`]
+    const MAX_CONCURRENT_REQUESTS: Option<usize> = None;
+
     /// Get all data rows for the table specified by the page index (starts a 0).
     ///
     /// If you return less than `PAGE_ROW_COUNT` rows, it is assumed that the end of the
     /// data has been reached.
     async fn get_page(&self, page_index: usize) -> Result<Vec<Row>, Err>;
 
+    /// Same as [`get_page`](PaginatedTableDataProvider::get_page) but also receives an [`AbortToken`].
+    /// See [`TableDataProvider::get_rows_with_context`] for details.
+    #[allow(unused_variables)]
+    async fn get_page_with_context(
+        &self,
+        page_index: usize,
+        abort_token: AbortToken,
+    ) -> Result<Vec<Row>, Err> {
+        self.get_page(page_index).await
+    }
+
     /// The total number of rows in the table. Returns `None` if unknown (which is the default).
     ///
     /// By default this is computed from the [`page_count`] method. But if your data source
@@ -103,6 +151,12 @@ pub trait PaginatedTableDataProvider<Row, Column, Err: Debug = String> {
         // by default do nothing
     }
 
+    /// Same as [`TableDataProvider::set_filter`]
+    #[allow(unused_variables)]
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        // by default do nothing
+    }
+
     /// Same as [`TableDataProvider::track`]
     fn track(&self) {
         // by default do nothing
@@ -116,16 +170,28 @@ where
 {
     const CHUNK_SIZE: Option<usize> = Some(D::PAGE_ROW_COUNT);
 
+    const MAX_CONCURRENT_REQUESTS: Option<usize> = D::MAX_CONCURRENT_REQUESTS;
+
     async fn get_rows(&self, range: Range<usize>) -> Result<(Vec<Row>, Range<usize>), Err> {
+        self.get_rows_with_context(range, AbortToken::new()).await
+    }
+
+    async fn get_rows_with_context(
+        &self,
+        range: Range<usize>,
+        abort_token: AbortToken,
+    ) -> Result<(Vec<Row>, Range<usize>), Err> {
         let Range { start, end } = range;
 
         debug_assert_eq!(start % D::PAGE_ROW_COUNT, 0);
         debug_assert_eq!(end - start, D::PAGE_ROW_COUNT);
 
-        self.get_page(start / D::PAGE_ROW_COUNT).await.map(|rows| {
-            let len = rows.len();
-            (rows, start..start + len)
-        })
+        self.get_page_with_context(start / D::PAGE_ROW_COUNT, abort_token)
+            .await
+            .map(|rows| {
+                let len = rows.len();
+                (rows, start..start + len)
+            })
     }
 
     async fn row_count(&self) -> Option<usize> {
@@ -136,6 +202,10 @@ where
         PaginatedTableDataProvider::<Row, Column, Err>::set_sorting(self, sorting)
     }
 
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        PaginatedTableDataProvider::<Row, Column, Err>::set_filter(self, filter)
+    }
+
     fn track(&self) {
         PaginatedTableDataProvider::<Row, Column, Err>::track(self)
     }
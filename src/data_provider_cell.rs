@@ -0,0 +1,223 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+type Mutation<DataP> = Box<dyn FnOnce(&mut DataP)>;
+
+/// Holds the data provider of a [`TableContent`] and is used internally to share it between
+/// the effects and the loading tasks.
+///
+/// Requests get their own handle to the provider so no borrow is held while awaiting them.
+/// Mutations like [`TableDataProvider::set_sorting`] can only be applied while no request is
+/// running. Until then they are queued and applied before the next request starts. This way
+/// no mutation is ever lost and no request ever sees a half updated provider.
+pub struct DataProviderCell<DataP> {
+    provider: RefCell<Rc<DataP>>,
+    pending_mutations: RefCell<Vec<Mutation<DataP>>>,
+    waiting: RefCell<Vec<Waker>>,
+}
+
+impl<DataP> DataProviderCell<DataP> {
+    pub fn new(provider: DataP) -> Rc<Self> {
+        Rc::new(Self {
+            provider: RefCell::new(Rc::new(provider)),
+            pending_mutations: RefCell::new(vec![]),
+            waiting: RefCell::new(vec![]),
+        })
+    }
+
+    /// Mutates the provider. This is done immediately if no request is running. Otherwise the
+    /// mutation is applied once all running requests are done and before the next one starts.
+    pub fn mutate(&self, mutation: impl FnOnce(&mut DataP) + 'static) {
+        self.pending_mutations.borrow_mut().push(Box::new(mutation));
+        self.apply_pending_mutations();
+    }
+
+    /// Calls `f` with the provider in its current state, ignoring pending mutations.
+    pub fn with<T>(&self, f: impl FnOnce(&DataP) -> T) -> T {
+        f(&self.provider.borrow())
+    }
+
+    /// Waits until all pending mutations are applied and returns a handle to the provider
+    /// that can be held across `.await`s.
+    pub fn provider(self: &Rc<Self>) -> ProviderFuture<DataP> {
+        ProviderFuture {
+            cell: Rc::clone(self),
+        }
+    }
+
+    /// Returns `true` if there are no pending mutations (anymore).
+    fn apply_pending_mutations(&self) -> bool {
+        if self.pending_mutations.borrow().is_empty() {
+            return true;
+        }
+
+        let mut provider = self.provider.borrow_mut();
+        let Some(provider) = Rc::get_mut(&mut provider) else {
+            // a request is still running
+            return false;
+        };
+
+        let mutations = self.pending_mutations.take();
+        for mutation in mutations {
+            mutation(provider);
+        }
+
+        true
+    }
+
+    fn wake_waiting(&self) {
+        let waiting = self.waiting.take();
+        for waker in waiting {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`DataProviderCell::provider`].
+pub struct ProviderFuture<DataP> {
+    cell: Rc<DataProviderCell<DataP>>,
+}
+
+impl<DataP> Future for ProviderFuture<DataP> {
+    type Output = ProviderRef<DataP>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let cell = &self.cell;
+
+        if cell.apply_pending_mutations() {
+            Poll::Ready(ProviderRef {
+                provider: Some(Rc::clone(&cell.provider.borrow())),
+                cell: Rc::clone(cell),
+            })
+        } else {
+            cell.waiting.borrow_mut().push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Handle to the data provider that is held for the duration of a request.
+pub struct ProviderRef<DataP> {
+    provider: Option<Rc<DataP>>,
+    cell: Rc<DataProviderCell<DataP>>,
+}
+
+impl<DataP> Deref for ProviderRef<DataP> {
+    type Target = DataP;
+
+    fn deref(&self) -> &Self::Target {
+        self.provider.as_ref().expect("only taken on drop")
+    }
+}
+
+impl<DataP> Drop for ProviderRef<DataP> {
+    fn drop(&mut self) {
+        drop(self.provider.take());
+
+        // this might have been the last running request
+        if self.cell.apply_pending_mutations() {
+            self.cell.wake_waiting();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Default)]
+    struct SortedProvider {
+        sorting: Vec<usize>,
+        release: Rc<Cell<bool>>,
+    }
+
+    impl SortedProvider {
+        async fn get_rows(&self) -> Vec<usize> {
+            let sorting = self.sorting.clone();
+            Release(Rc::clone(&self.release)).await;
+            sorting
+        }
+    }
+
+    struct Release(Rc<Cell<bool>>);
+
+    impl Future for Release {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.0.get() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    fn poll<F: Future>(future: &mut Pin<Box<F>>) -> Poll<F::Output> {
+        future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    async fn get_rows(cell: Rc<DataProviderCell<SortedProvider>>) -> Vec<usize> {
+        cell.provider().await.get_rows().await
+    }
+
+    #[test]
+    fn sorting_is_applied_immediately_when_idle() {
+        let cell = DataProviderCell::new(SortedProvider::default());
+
+        cell.mutate(|provider| provider.sorting = vec![1]);
+
+        assert_eq!(cell.with(|provider| provider.sorting.clone()), vec![1]);
+    }
+
+    #[test]
+    fn sorting_during_load_is_used_by_next_load() {
+        let release = Rc::new(Cell::new(false));
+        let cell = DataProviderCell::new(SortedProvider {
+            sorting: vec![0],
+            release: Rc::clone(&release),
+        });
+
+        let mut running = Box::pin(get_rows(Rc::clone(&cell)));
+        assert!(poll(&mut running).is_pending());
+
+        // sort while the first load is still in progress
+        cell.mutate(|provider| provider.sorting = vec![2, 1]);
+        assert_eq!(cell.with(|provider| provider.sorting.clone()), vec![0]);
+
+        // the next load has to wait for the new sorting
+        let mut next = Box::pin(get_rows(Rc::clone(&cell)));
+        assert!(poll(&mut next).is_pending());
+
+        release.set(true);
+        assert_eq!(poll(&mut running), Poll::Ready(vec![0]));
+        assert_eq!(poll(&mut next), Poll::Ready(vec![2, 1]));
+    }
+
+    #[test]
+    fn multiple_mutations_during_load_are_applied_in_order() {
+        let release = Rc::new(Cell::new(false));
+        let cell = DataProviderCell::new(SortedProvider {
+            sorting: vec![],
+            release: Rc::clone(&release),
+        });
+
+        let mut running = Box::pin(get_rows(Rc::clone(&cell)));
+        assert!(poll(&mut running).is_pending());
+
+        cell.mutate(|provider| provider.sorting.push(1));
+        cell.mutate(|provider| provider.sorting.push(2));
+
+        release.set(true);
+        assert_eq!(poll(&mut running), Poll::Ready(vec![]));
+
+        assert_eq!(cell.with(|provider| provider.sorting.clone()), vec![1, 2]);
+    }
+}
@@ -7,18 +7,24 @@ use std::sync::Arc;
 pub struct ChangeEvent<Row: Send + Sync + 'static> {
     /// The index of the table row that contains the cell that was changed. Starts at 0.
     pub row_index: usize,
+    /// The key of the row that was changed as returned by the `row_key` prop of
+    /// [`TableContent`](crate::TableContent). `None` if the prop isn't given.
+    /// Unlike the index this stays the same when the table is sorted or reloaded.
+    pub row_key: Option<String>,
     /// The the row that was changed.
     pub changed_row: Signal<Row>,
 }
 
 impl<Row: Send + Sync + 'static> Clone for ChangeEvent<Row> {
     fn clone(&self) -> Self {
-        *self
+        Self {
+            row_index: self.row_index,
+            row_key: self.row_key.clone(),
+            changed_row: self.changed_row,
+        }
     }
 }
 
-impl<Row: Send + Sync + 'static> Copy for ChangeEvent<Row> {}
-
 /// The event provided to the `on_selection_change` prop of the table component
 #[derive(Debug)]
 pub struct SelectionChangeEvent<Row: Send + Sync + 'static> {
@@ -26,18 +32,24 @@ pub struct SelectionChangeEvent<Row: Send + Sync + 'static> {
     pub selected: bool,
     /// The index of the row that was de-/selected.
     pub row_index: usize,
+    /// The key of the row that was de-/selected as returned by the `row_key` prop of
+    /// [`TableContent`](crate::TableContent). `None` if the prop isn't given.
+    pub row_key: Option<String>,
     /// The row that was de-/selected.
     pub row: Signal<Row>,
 }
 
 impl<Row: Send + Sync + 'static> Clone for SelectionChangeEvent<Row> {
     fn clone(&self) -> Self {
-        *self
+        Self {
+            selected: self.selected,
+            row_index: self.row_index,
+            row_key: self.row_key.clone(),
+            row: self.row,
+        }
     }
 }
 
-impl<Row: Send + Sync + 'static> Copy for SelectionChangeEvent<Row> {}
-
 /// Event emitted when a table head cell is clicked.
 #[derive(Debug)]
 pub struct TableHeadEvent<Column> {
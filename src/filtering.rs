@@ -0,0 +1,398 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// A value that is compared against the values of a column by a [`ColumnFilter`].
+///
+/// `Int` and `Float` values can be compared with each other. Values of other different variants
+/// are not comparable.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum FilterValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl PartialOrd for FilterValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (FilterValue::Bool(a), FilterValue::Bool(b)) => a.partial_cmp(b),
+            (FilterValue::Int(a), FilterValue::Int(b)) => a.partial_cmp(b),
+            (FilterValue::Float(a), FilterValue::Float(b)) => a.partial_cmp(b),
+            (FilterValue::Int(a), FilterValue::Float(b)) => (*a as f64).partial_cmp(b),
+            (FilterValue::Float(a), FilterValue::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (FilterValue::Text(a), FilterValue::Text(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+impl Display for FilterValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterValue::Bool(value) => write!(f, "{value}"),
+            FilterValue::Int(value) => write!(f, "{value}"),
+            FilterValue::Float(value) => write!(f, "{value}"),
+            FilterValue::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+macro_rules! filter_value_from {
+    ($variant:ident($target:ty): $($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for FilterValue {
+                fn from(value: $ty) -> Self {
+                    FilterValue::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+filter_value_from!(Int(i64): i8, i16, i32, i64, isize, u8, u16, u32);
+filter_value_from!(Float(f64): f32, f64);
+
+/// Values that don't fit into an `i64` are converted to a `Float`.
+macro_rules! filter_value_from_unsigned {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl From<$ty> for FilterValue {
+                fn from(value: $ty) -> Self {
+                    i64::try_from(value)
+                        .map(FilterValue::Int)
+                        .unwrap_or(FilterValue::Float(value as f64))
+                }
+            }
+        )*
+    };
+}
+
+filter_value_from_unsigned!(u64, usize);
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        FilterValue::Bool(value)
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        FilterValue::Text(value)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        FilterValue::Text(value.to_string())
+    }
+}
+
+/// A predicate on the values of a single column. Used in [`FilterModel`].
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum ColumnFilter {
+    /// The value of the column contains this text (case insensitive).
+    Contains(String),
+
+    /// The value of the column is equal to this value.
+    Equals(FilterValue),
+
+    /// The value of the column is inside of this range. Both bounds are inclusive and
+    /// `None` means unbounded.
+    Range {
+        min: Option<FilterValue>,
+        max: Option<FilterValue>,
+    },
+
+    /// The value of the column is equal to one of these values.
+    InSet(Vec<FilterValue>),
+}
+
+impl ColumnFilter {
+    /// Returns `true` if the `value` of a cell passes this filter. `None` never passes.
+    pub fn matches(&self, value: Option<&FilterValue>) -> bool {
+        let Some(value) = value else {
+            return false;
+        };
+
+        let is_equal = |other: &FilterValue| value.partial_cmp(other) == Some(Ordering::Equal);
+
+        match self {
+            ColumnFilter::Contains(text) => contains_ignore_case(&value.to_string(), text),
+            ColumnFilter::Equals(other) => is_equal(other),
+            ColumnFilter::Range { min, max } => {
+                min.as_ref().is_none_or(|min| {
+                    matches!(
+                        value.partial_cmp(min),
+                        Some(Ordering::Greater | Ordering::Equal)
+                    )
+                }) && max.as_ref().is_none_or(|max| {
+                    matches!(
+                        value.partial_cmp(max),
+                        Some(Ordering::Less | Ordering::Equal)
+                    )
+                })
+            }
+            ColumnFilter::InSet(values) => values.iter().any(is_equal),
+        }
+    }
+}
+
+fn contains_ignore_case(text: &str, search: &str) -> bool {
+    text.to_lowercase().contains(&search.to_lowercase())
+}
+
+/// Implement this for your row type to be able to filter it on the client with
+/// [`VecDataProvider`].
+pub trait FilterableRow<Column> {
+    /// Returns the value of the given column that filters are matched against. Return `None` if
+    /// the cell is empty or the column can't be filtered.
+    fn filter_value(&self, column: Column) -> Option<FilterValue>;
+}
+
+/// The filters of a table. Pass it as an `RwSignal` to the `filter` prop of [`TableContent`].
+/// Every time it changes, [`TableDataProvider::set_filter`] is called and the table is reloaded.
+///
+/// A row has to match all column filters and the global search to be shown.
+/// It's up to the data provider to apply the filters to its data source.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_struct_table::*;
+/// #
+/// let filter = RwSignal::new(FilterModel::<usize>::default());
+///
+/// filter.update(|filter| {
+///     filter.set(1, ColumnFilter::Contains("tolkien".to_string()));
+///     filter.set(2, ColumnFilter::Range { min: Some(1950.into()), max: None });
+///     filter.search = "ring".to_string();
+/// });
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FilterModel<Column> {
+    /// The filter for every filtered column.
+    pub columns: Vec<(Column, ColumnFilter)>,
+
+    /// Search string that is matched against all columns. Empty if there's no global search.
+    pub search: String,
+}
+
+impl<Column> Default for FilterModel<Column> {
+    fn default() -> Self {
+        Self {
+            columns: vec![],
+            search: String::new(),
+        }
+    }
+}
+
+impl<Column> FilterModel<Column> {
+    /// Returns `true` if nothing is filtered.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty() && self.search.is_empty()
+    }
+}
+
+impl<Column: PartialEq> FilterModel<Column> {
+    /// Sets the filter of `column` replacing the previous one if there was any.
+    pub fn set(&mut self, column: Column, filter: ColumnFilter) {
+        self.remove(&column);
+        self.columns.push((column, filter));
+    }
+
+    /// Removes the filter of `column`.
+    pub fn remove(&mut self, column: &Column) {
+        self.columns.retain(|(col, _)| col != column);
+    }
+
+    /// Returns the filter of `column` if there is one.
+    pub fn get(&self, column: &Column) -> Option<&ColumnFilter> {
+        self.columns
+            .iter()
+            .find(|(col, _)| col == column)
+            .map(|(_, filter)| filter)
+    }
+
+    /// Removes all column filters and the global search.
+    pub fn clear(&mut self) {
+        self.columns.clear();
+        self.search.clear();
+    }
+}
+
+impl<Column: Copy> FilterModel<Column> {
+    /// Returns `true` if the row passes all column filters and the global search.
+    /// `searched_columns` are the columns that the global search is matched against.
+    pub fn matches<Row>(&self, row: &Row, searched_columns: &[Column]) -> bool
+    where
+        Row: FilterableRow<Column>,
+    {
+        let columns_match = self
+            .columns
+            .iter()
+            .all(|(column, filter)| filter.matches(row.filter_value(*column).as_ref()));
+
+        columns_match
+            && (self.search.is_empty()
+                || searched_columns.iter().any(|column| {
+                    row.filter_value(*column)
+                        .is_some_and(|value| contains_ignore_case(&value.to_string(), &self.search))
+                }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Book {
+        title: &'static str,
+        year: Option<u16>,
+    }
+
+    impl FilterableRow<usize> for Book {
+        fn filter_value(&self, column: usize) -> Option<FilterValue> {
+            match column {
+                0 => Some(self.title.into()),
+                1 => self.year.map(Into::into),
+                _ => None,
+            }
+        }
+    }
+
+    const HOBBIT: Book = Book {
+        title: "The Hobbit",
+        year: Some(1937),
+    };
+    const SILMARILLION: Book = Book {
+        title: "The Silmarillion",
+        year: None,
+    };
+
+    #[test]
+    fn contains_ignores_case() {
+        let filter = ColumnFilter::Contains("HOB".to_string());
+
+        assert!(filter.matches(Some(&"The Hobbit".into())));
+        assert!(!filter.matches(Some(&"The Silmarillion".into())));
+        assert!(ColumnFilter::Contains("193".to_string()).matches(Some(&1937.into())));
+    }
+
+    #[test]
+    fn equals_compares_ints_and_floats() {
+        let filter = ColumnFilter::Equals(2.into());
+
+        assert!(filter.matches(Some(&2.into())));
+        assert!(filter.matches(Some(&2.0.into())));
+        assert!(!filter.matches(Some(&2.5.into())));
+        assert!(!filter.matches(Some(&"2".into())));
+    }
+
+    #[test]
+    fn range_bounds_are_inclusive_and_optional() {
+        let filter = ColumnFilter::Range {
+            min: Some(10.into()),
+            max: Some(20.into()),
+        };
+
+        assert!(filter.matches(Some(&10.into())));
+        assert!(filter.matches(Some(&20.into())));
+        assert!(!filter.matches(Some(&9.into())));
+        assert!(!filter.matches(Some(&20.5.into())));
+
+        let open = ColumnFilter::Range {
+            min: None,
+            max: Some(20.into()),
+        };
+        assert!(open.matches(Some(&i64::MIN.into())));
+        assert!(!open.matches(Some(&21.into())));
+
+        // values of other types are not comparable
+        assert!(!open.matches(Some(&"10".into())));
+    }
+
+    #[test]
+    fn in_set_matches_any_value() {
+        let filter = ColumnFilter::InSet(vec!["a".into(), "b".into()]);
+
+        assert!(filter.matches(Some(&"b".into())));
+        assert!(!filter.matches(Some(&"c".into())));
+        assert!(!ColumnFilter::InSet(vec![]).matches(Some(&"a".into())));
+    }
+
+    #[test]
+    fn none_never_matches() {
+        assert!(!ColumnFilter::Contains(String::new()).matches(None));
+        assert!(
+            !ColumnFilter::Range {
+                min: None,
+                max: None
+            }
+            .matches(None)
+        );
+    }
+
+    #[test]
+    fn set_replaces_the_filter_of_a_column() {
+        let mut model = FilterModel::default();
+        assert!(model.is_empty());
+
+        model.set(1, ColumnFilter::Equals(1937.into()));
+        model.set(0, ColumnFilter::Contains("hobbit".to_string()));
+        model.set(1, ColumnFilter::Equals(1954.into()));
+
+        assert_eq!(model.columns.len(), 2);
+        assert_eq!(model.get(&1), Some(&ColumnFilter::Equals(1954.into())));
+
+        model.remove(&1);
+        assert_eq!(model.get(&1), None);
+        assert!(!model.is_empty());
+
+        model.search = "ring".to_string();
+        model.clear();
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn row_has_to_match_all_column_filters() {
+        let mut model = FilterModel::default();
+        assert!(model.matches(&HOBBIT, &[0, 1]));
+
+        model.set(0, ColumnFilter::Contains("the".to_string()));
+        assert!(model.matches(&HOBBIT, &[0, 1]));
+        assert!(model.matches(&SILMARILLION, &[0, 1]));
+
+        model.set(
+            1,
+            ColumnFilter::Range {
+                min: Some(1900.into()),
+                max: None,
+            },
+        );
+        assert!(model.matches(&HOBBIT, &[0, 1]));
+        assert!(!model.matches(&SILMARILLION, &[0, 1]));
+    }
+
+    #[test]
+    fn search_matches_any_of_the_searched_columns() {
+        let model = FilterModel {
+            columns: vec![],
+            search: "1937".to_string(),
+        };
+
+        assert!(model.matches(&HOBBIT, &[0, 1]));
+        assert!(!model.matches(&HOBBIT, &[0]));
+        assert!(!model.matches(&SILMARILLION, &[0, 1]));
+    }
+
+    #[test]
+    fn from_unsigned_integers() {
+        assert_eq!(FilterValue::from(42_u64), FilterValue::Int(42));
+        assert_eq!(FilterValue::from(42_usize), FilterValue::Int(42));
+        assert_eq!(
+            FilterValue::from(u64::MAX),
+            FilterValue::Float(u64::MAX as f64)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use wasm_bindgen::JsCast;
+
+/// The position of the cell that can be focused with the keyboard. `row` is the index of the row
+/// and `col` the position of the cell in the rendered row (including the selection column).
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub(crate) struct CellPosition {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// The `aria-rowindex` of the body row at `row_index`. ARIA indices start at 1 and the head row comes first.
+pub(crate) fn aria_row_index(row_index: usize) -> usize {
+    row_index + 2
+}
+
+/// Returns the cell that the focus moves to when `key` is pressed in the cell `current`.
+/// `page_size` is the number of rows that PageUp and PageDown move.
+/// Returns `None` if the key isn't used for navigation.
+pub(crate) fn next_cell_position(
+    key: &str,
+    ctrl_pressed: bool,
+    current: CellPosition,
+    row_count: usize,
+    col_count: usize,
+    page_size: usize,
+) -> Option<CellPosition> {
+    if row_count == 0 || col_count == 0 {
+        return None;
+    }
+
+    let last_row = row_count - 1;
+    let last_col = col_count - 1;
+    let CellPosition { row, col } = current;
+
+    let (row, col) = match key {
+        "ArrowUp" => (row.saturating_sub(1), col),
+        "ArrowDown" => (row + 1, col),
+        "ArrowLeft" => (row, col.saturating_sub(1)),
+        "ArrowRight" => (row, col + 1),
+        "Home" if ctrl_pressed => (0, col),
+        "End" if ctrl_pressed => (last_row, col),
+        "Home" => (row, 0),
+        "End" => (row, last_col),
+        "PageUp" => (row.saturating_sub(page_size.max(1)), col),
+        "PageDown" => (row + page_size.max(1), col),
+        _ => return None,
+    };
+
+    Some(CellPosition {
+        row: row.min(last_row),
+        col: col.min(last_col),
+    })
+}
+
+/// Finds the cell at `position` among the rendered rows of `tbody`.
+/// The rows are identified by their `aria-rowindex` attribute.
+pub(crate) fn find_cell(
+    tbody: &web_sys::Element,
+    position: CellPosition,
+) -> Option<web_sys::Element> {
+    tbody
+        .query_selector(&format!(
+            ":scope > tr[aria-rowindex=\"{}\"]",
+            aria_row_index(position.row)
+        ))
+        .ok()
+        .flatten()
+        .and_then(|row| row.children().item(position.col as u32))
+}
+
+/// Finds the first cell in column `col` of the first rendered row of `tbody`.
+pub(crate) fn find_first_cell(tbody: &web_sys::Element, col: usize) -> Option<web_sys::Element> {
+    tbody
+        .query_selector(":scope > tr[aria-rowindex]")
+        .ok()
+        .flatten()
+        .and_then(|row| row.children().item(col as u32))
+}
+
+/// Returns the position of the cell that contains the event target.
+pub(crate) fn cell_position_of(target: Option<web_sys::EventTarget>) -> Option<CellPosition> {
+    let cell = target?
+        .dyn_into::<web_sys::Element>()
+        .ok()?
+        .closest("tr[aria-rowindex] > *")
+        .ok()
+        .flatten()?;
+
+    let row = cell.parent_element()?;
+    let aria_row_index = row.get_attribute("aria-rowindex")?.parse::<usize>().ok()?;
+
+    let children = row.children();
+    let col = (0..children.length()).find(|i| children.item(*i).as_ref() == Some(&cell))?;
+
+    Some(CellPosition {
+        row: aria_row_index.checked_sub(2)?,
+        col: col as usize,
+    })
+}
+
+/// Returns `true` if the event target is an element like an input that handles
+/// the keyboard itself.
+pub(crate) fn is_editable_target(target: Option<web_sys::EventTarget>) -> bool {
+    let Some(element) = target.and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return false;
+    };
+
+    element.is_content_editable()
+        || matches!(
+            element.tag_name().as_str(),
+            "INPUT" | "TEXTAREA" | "SELECT" | "BUTTON"
+        )
+}
+
+/// Makes `cell` the only cell that can be reached with the tab key (roving tabindex) and
+/// removes `previous` from the tab order.
+pub(crate) fn set_tabbable_cell(cell: &web_sys::Element, previous: Option<&web_sys::Element>) {
+    if let Some(previous) = previous
+        && previous != cell
+    {
+        let _ = previous.set_attribute("tabindex", "-1");
+    }
+    let _ = cell.set_attribute("tabindex", "0");
+}
+
+pub(crate) fn focus_cell(cell: &web_sys::Element) {
+    if let Some(cell) = cell.dyn_ref::<web_sys::HtmlElement>() {
+        let _ = cell.focus();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(key: &str, ctrl: bool, row: usize, col: usize) -> Option<(usize, usize)> {
+        next_cell_position(key, ctrl, CellPosition { row, col }, 100, 4, 10)
+            .map(|position| (position.row, position.col))
+    }
+
+    #[test]
+    fn arrow_keys_move_within_bounds() {
+        assert_eq!(next("ArrowDown", false, 5, 1), Some((6, 1)));
+        assert_eq!(next("ArrowUp", false, 0, 1), Some((0, 1)));
+        assert_eq!(next("ArrowRight", false, 5, 3), Some((5, 3)));
+        assert_eq!(next("ArrowLeft", false, 5, 0), Some((5, 0)));
+        assert_eq!(next("ArrowDown", false, 99, 2), Some((99, 2)));
+    }
+
+    #[test]
+    fn home_end_and_pages() {
+        assert_eq!(next("Home", false, 5, 2), Some((5, 0)));
+        assert_eq!(next("End", false, 5, 0), Some((5, 3)));
+        assert_eq!(next("Home", true, 5, 2), Some((0, 2)));
+        assert_eq!(next("End", true, 5, 2), Some((99, 2)));
+        assert_eq!(next("PageDown", false, 95, 0), Some((99, 0)));
+        assert_eq!(next("PageUp", false, 15, 0), Some((5, 0)));
+        assert_eq!(next("a", false, 5, 0), None);
+    }
+}
@@ -6,7 +6,7 @@
 //!
 //! - **Easy to use** - yet powerful.
 //! - **Async data loading** - The data is loaded asynchronously. This allows to load data from a REST API or a database etc.
-//! - **Selection** - Can be turned off or single/multi select
+//! - **Selection** - Can be turned off or single/multi select with an optional checkbox column
 //! - **Customization** - You can customize every aspect of the table by plugging in your own components for rendering rows, cells, headers. See [Custom Renderers](#custom-renderers) for more information.
 //! - **Headless** - No default styling is applied to the table. You can fully customize the classes that are applied to the table. See [Classes customization](#classes-customization) for more information.
 //! - **Sorting** - Optional. If turned on: Click on a column header to sort the table by that column. You can even sort by multiple columns.
@@ -14,6 +14,7 @@
 //! - **Pagination** - Instead of virtualization you can paginate the table.
 //! - **Caching** - Only visible rows are loaded and cached.
 //! - **Editing** - Optional. You can provide custom renderers for editable cells. See [Editable Cells](#editable-cells) for more information.
+//! - **Accessibility** - Keyboard navigation between cells and ARIA grid attributes that stay correct for virtualized rows.
 //!
 //! # Usage
 //!
@@ -97,6 +98,14 @@
 //! and the [serverfn_sqlx example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/serverfn_sqlx/src/data_provider.rs)
 //! for working demo projects that implement these traits.
 //!
+//! If your data source uses cursors (keyset pagination) instead of offsets, implement [`CursorTableDataProvider`]
+//! and wrap it in a [`CursorDataProvider`]. It keeps track of the cursor of every loaded page and discards them
+//! when the sorting or the filter changes.
+//!
+//! If you keep your data in memory but need more control than `impl_vec_data_provider` gives you,
+//! you can use [`sort_rows`] in your implementation of [`TableDataProvider::set_sorting`] to sort it.
+//! Or implement [`SortableRow`] for your row type to define how each column is sorted and use [`sort_rows_by_column`].
+//!
 //! # Macro options
 //!
 //! The `#[table(...)]` attribute can be used to customize the generated component. The following options are available:
@@ -180,6 +189,7 @@ pub struct TemperatureMeasurement {
 //! - **`rust_decimal`** - Adds support for types from the crate `rust_decimal`.
 //! - **`time`** - Adds support for types from the crate `time`.
 //! - **`uuid`** - Adds support for types from the crate `uuid`.
+//! - **`xlsx`** - Adds the module `xlsx` to export tables to Excel files with typed cells using the crate `rust_xlsxwriter`.
 //!
 //! # Classes Customization
 //!
@@ -445,6 +455,23 @@ pub struct TemperatureMeasurement {
 //!
 //! Please have a look at the [pagination example](https://github.com/Synphonyte/leptos-struct-table/tree/master/examples/pagination/src/main.rs) for more information on how to use pagination.
 //!
+//! # Filtering
+//!
+//! Pass an `RwSignal<FilterModel<Column>>` to the `filter` prop of the [`TableContent`] component to filter the table.
+//! A [`FilterModel`] holds a [`ColumnFilter`] per column as well as a global search string.
+//! Every time it changes, [`TableDataProvider::set_filter`] is called and the table is reloaded.
+//! It's up to your data provider to apply the filter to its data source.
+//!
+//! For local data you can wrap your `Vec` of rows in a [`VecDataProvider`] which filters and sorts on the client.
+//! Your row type has to implement [`FilterableRow`] and [`SortableRow`] for that. The filtered row count is reported by `row_count()`
+//! and [`VecDataProvider::original_index`] maps the displayed row indices back to the indices in your `Vec`.
+//!
+//! If your data comes from an SQL database you can use the [`SqlBuilder`] to turn the filter, the sorting and the
+//! requested row range into a parameterized `WHERE ... ORDER BY ... LIMIT ... OFFSET ...` for Postgres, MySQL or SQLite.
+//! Column names are quoted and all values are returned as bind values. The columns are named like the fields
+//! unless you map them to differently named columns or SQL expressions with [`SqlBuilder::map_columns`]. Please have a look at the
+//! [serverfn_sqlx example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/serverfn_sqlx/src/data_provider.rs).
+//!
 //! # I18n
 //!
 //! To translate the column titles of the table using `leptos-i18n` you can enable the `"i18n"`
@@ -461,40 +488,67 @@ pub struct TemperatureMeasurement {
 
 #![allow(non_snake_case)]
 
+// makes the derive macro usable in the tests of this crate
+#[cfg(test)]
+extern crate self as leptos_struct_table;
+
+mod abort_token;
+mod cell_selection;
 mod cell_value;
 #[cfg(feature = "chrono")]
 pub mod chrono;
 mod class_providers;
+mod clipboard;
 mod components;
+mod csv_export;
+mod cursor_data_provider;
 mod data_provider;
+mod data_provider_cell;
 mod display_strategy;
 mod events;
+mod filtering;
+mod keyboard_navigation;
 mod loaded_rows;
+mod loading_queue;
+mod number_format;
 mod reload_controller;
 mod row_reader;
 #[cfg(feature = "rust_decimal")]
 pub mod rust_decimal;
 mod selection;
 mod sorting;
+mod sql;
 mod table_row;
 #[cfg(feature = "time")]
 pub mod time;
 #[cfg(feature = "uuid")]
 mod uuid;
+mod vec_data_provider;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
 
+pub use abort_token::*;
+pub use cell_selection::*;
 pub use cell_value::*;
 pub use class_providers::*;
+pub use clipboard::*;
 pub use components::*;
+pub use csv_export::*;
+pub use cursor_data_provider::*;
 pub use data_provider::*;
 pub use display_strategy::*;
 pub use events::*;
+pub use filtering::*;
 pub use leptos_struct_table_macro::TableRow;
-pub use loaded_rows::RowState;
+pub use loaded_rows::{CacheMetrics, RowState};
+pub use number_format::{CurrencyPlacement, Notation, NumberLocale, SignDisplay};
 pub use reload_controller::*;
 pub use row_reader::*;
 pub use selection::*;
 pub use sorting::*;
+pub use sql::*;
 pub use table_row::*;
+pub use vec_data_provider::*;
 
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
@@ -525,6 +579,15 @@ impl ColumnSort {
             _ => None,
         }
     }
+
+    /// Returns the value of the `aria-sort` attribute of the column header.
+    pub fn as_aria_sort(&self) -> &'static str {
+        match self {
+            ColumnSort::Ascending => "ascending",
+            ColumnSort::Descending => "descending",
+            ColumnSort::None => "none",
+        }
+    }
 }
 
 /// Type of struct field used to specify that the value of this field is
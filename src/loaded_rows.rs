@@ -1,4 +1,5 @@
 use leptos::prelude::*;
+use std::collections::{BTreeMap, HashSet};
 use std::ops::{Index, Range};
 
 pub enum RowState<T: Send + Sync + 'static> {
@@ -34,35 +35,139 @@ impl<T: Send + Sync + 'static> std::fmt::Debug for RowState<T> {
     }
 }
 
+/// Statistics about the row cache of a [`TableContent`]. Can be read through [`RowReader::cache_metrics`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// The number of rows that are currently loaded and kept in the cache.
+    pub loaded_rows: usize,
+    /// How many times a row that was needed for display was already loaded.
+    pub hits: u64,
+    /// How many times a row that was needed for display had to be loaded.
+    pub misses: u64,
+    /// The number of loaded rows that have been evicted from the cache to stay within the
+    /// `max_loaded_rows` limit of [`TableContent`].
+    pub evictions: u64,
+}
+
 /// This is basically a cache for rows and used by [`TableContent`] internally to track
 /// which rows are already loaded, which are still loading and which are missing.
+///
+/// Only rows that are not `RowState::Placeholder` are stored. They are kept in runs of consecutive
+/// rows so a range of stored rows can still be indexed as a slice. This way the memory used doesn't
+/// depend on the total number of rows but only on the number of rows that have been loaded.
 pub struct LoadedRows<T: Send + Sync + 'static> {
-    rows: Vec<RowState<T>>,
+    /// Runs of consecutive rows by the index of their first row. Runs never overlap or touch and
+    /// don't contain placeholders between two operations.
+    runs: BTreeMap<usize, Vec<RowState<T>>>,
+    /// The total number of rows. All rows up to this that are not in `runs` are placeholders.
+    len: usize,
+    /// The number of `RowState::Loaded` rows in `runs`.
+    loaded: usize,
+    /// Returned by the `Index` impl for rows that are not stored.
+    placeholder: RowState<T>,
+    /// The rows that have been edited. These are never evicted. They are identified by their signal
+    /// and not by their index so the mark stays with the row and isn't inherited by a row that is
+    /// loaded at the same index later.
+    dirty_rows: HashSet<RwSignal<T>>,
+    /// The loaded rows whose signals have been updated by a soft reload and not been edited.
+    refreshed_rows: HashSet<RwSignal<T>>,
+    /// The range given to the last call of [`Self::record_access`].
+    accessed_range: Range<usize>,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
 }
 
 impl<T: Send + Sync + 'static> LoadedRows<T> {
     pub fn new() -> Self {
-        Self { rows: vec![] }
+        Self {
+            runs: BTreeMap::new(),
+            len: 0,
+            loaded: 0,
+            placeholder: RowState::Placeholder,
+            dirty_rows: HashSet::new(),
+            refreshed_rows: HashSet::new(),
+            accessed_range: 0..0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.rows.len()
+        self.len
     }
 
-    #[inline]
     pub fn resize(&mut self, len: usize) {
-        self.rows.resize(len, RowState::Placeholder);
+        self.len = len;
+
+        if let Some((&start, run)) = self.runs.range_mut(..len).next_back()
+            && start + run.len() > len
+        {
+            let removed = run.split_off(len - start);
+            removed.iter().for_each(|row| self.forget(row));
+        }
+
+        for run in self.runs.split_off(&len).into_values() {
+            run.iter().for_each(|row| self.forget(row));
+        }
+    }
+
+    /// Iterates over the rows in `range` together with their indices. `range` is clamped to the length.
+    pub fn iter_range(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (usize, &RowState<T>)> + '_ {
+        let range = range.start.min(self.len)..range.end.min(self.len);
+        range.map(|index| (index, &self[index]))
+    }
+
+    /// Returns the rows in `range`. Rows that aren't stored are returned as `RowState::Placeholder`.
+    /// `range` is clamped to the length. Use this instead of indexing with a range.
+    pub fn rows_in(&self, range: Range<usize>) -> impl Iterator<Item = RowState<T>> + '_ {
+        self.iter_range(range).map(|(_, row)| row.clone())
+    }
+
+    /// Iterates over all loaded rows together with their indices.
+    pub fn iter_loaded(&self) -> impl Iterator<Item = (usize, RwSignal<T>)> + '_ {
+        self.iter_stored().filter_map(|(index, row)| match row {
+            RowState::Loaded(row) => Some((index, *row)),
+            _ => None,
+        })
+    }
+
+    /// Returns the rows in `range` as a slice if they are all stored, i.e. none of them is a
+    /// `RowState::Placeholder`.
+    pub fn get_range(&self, range: Range<usize>) -> Option<&[RowState<T>]> {
+        if range.is_empty() {
+            return Some(&[]);
+        }
+
+        let (start, run) = self.runs.range(..=range.start).next_back()?;
+        run.get(range.start - start..range.end - start)
     }
 
     pub fn write_loading(&mut self, range: Range<usize>) {
-        if range.end > self.rows.len() {
-            self.rows.resize(range.end, RowState::Placeholder);
+        if range.end > self.len {
+            self.len = range.end;
         }
 
-        for row in &mut self.rows[range] {
-            *row = RowState::Loading;
+        self.write_range(range, |_| RowState::Loading);
+    }
+
+    /// Resets rows that are still loading back to `Placeholder` so they are loaded again
+    /// once they are needed.
+    pub fn write_placeholder(&mut self, range: Range<usize>) {
+        let range = range.start.min(self.len)..range.end.min(self.len);
+
+        for index in range {
+            if matches!(self.get(index), Some(RowState::Loading)) {
+                self.set(index, RowState::Placeholder);
+            }
         }
+
+        self.compact();
     }
 
     pub fn write_loaded(
@@ -72,54 +177,346 @@ impl<T: Send + Sync + 'static> LoadedRows<T> {
     ) {
         match loading_result {
             Ok((rows, range)) => {
-                if range.end > self.rows.len() {
-                    self.rows.resize(range.end, RowState::Placeholder);
-                }
+                // don't store placeholders if fewer rows than requested have been returned
+                let range = range.start..range.end.min(range.start + rows.len());
 
-                for (self_row, loaded_row) in self.rows[range].iter_mut().zip(rows) {
-                    *self_row = RowState::Loaded(RwSignal::new(loaded_row));
+                if range.end > self.len {
+                    self.len = range.end;
                 }
+
+                let mut rows = rows.into_iter();
+                self.write_range(range, |_| {
+                    RowState::Loaded(RwSignal::new(rows.next().expect("range is clamped")))
+                });
             }
             Err(error) => {
-                let range = missing_range.start..missing_range.end.min(self.rows.len());
+                let range = missing_range.start..missing_range.end.min(self.len);
                 if range.start >= range.end {
                     return;
                 }
 
-                for row in &mut self.rows[range] {
-                    *row = RowState::Error(error.clone());
+                self.write_range(range, |_| RowState::Error(error.clone()));
+            }
+        }
+    }
+
+    /// Resets all rows in `range` to `Placeholder` so they are loaded again once they are needed.
+    pub fn invalidate(&mut self, range: Range<usize>) {
+        let indices = self
+            .iter_stored()
+            .map(|(index, _)| index)
+            .filter(|index| range.contains(index))
+            .collect::<Vec<_>>();
+
+        for index in indices {
+            let row = self.set(index, RowState::Placeholder);
+            self.forget(&row);
+        }
+
+        self.compact();
+    }
+
+    /// Discards all loaded and failed rows outside of `range` so they are loaded again once they
+    /// are needed. Rows that are still loading and edited rows are kept.
+    pub fn retain_range(&mut self, range: Range<usize>) {
+        let discarded = self
+            .iter_stored()
+            .filter(|(index, row)| {
+                !range.contains(index) && !self.is_dirty(row) && !matches!(row, RowState::Loading)
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        for index in discarded {
+            self.remove_and_dispose(index);
+        }
+
+        self.compact();
+    }
+
+    /// Writes the result of a soft reload. Rows that failed to load are replaced. For rows that are
+    /// loaded already the signal and the new row are returned so the signal can be updated in place.
+    /// Edited rows are kept as they are.
+    pub fn write_refreshed(&mut self, rows: Vec<T>, range: Range<usize>) -> Vec<(RwSignal<T>, T)> {
+        let mut updates = vec![];
+
+        for (index, row) in range.zip(rows) {
+            if index >= self.len {
+                continue;
+            }
+
+            match self.get(index) {
+                Some(RowState::Loaded(signal)) => {
+                    let signal = *signal;
+                    if !self.dirty_rows.contains(&signal) {
+                        self.refreshed_rows.insert(signal);
+                        updates.push((signal, row));
+                    }
+                }
+                Some(RowState::Error(_)) => {
+                    self.set(index, RowState::Loaded(RwSignal::new(row)));
                 }
+                Some(RowState::Loading | RowState::Placeholder) | None => {}
             }
         }
+
+        updates
     }
 
-    #[inline]
-    pub fn missing_range(&self, range: Range<usize>) -> Option<Range<usize>> {
-        let do_load_predicate = |row| matches!(row, &RowState::Placeholder);
+    /// Returns `true` if the last change of the row was done by [`Self::write_refreshed`]
+    /// instead of an edit.
+    pub fn take_refreshed(&mut self, row: RwSignal<T>) -> bool {
+        self.refreshed_rows.remove(&row)
+    }
+
+    /// Marks the row as edited so it is never evicted.
+    pub fn mark_dirty(&mut self, row: RwSignal<T>) {
+        self.dirty_rows.insert(row);
+    }
+
+    fn is_dirty(&self, row: &RowState<T>) -> bool {
+        matches!(row, RowState::Loaded(row) if self.dirty_rows.contains(row))
+    }
+
+    /// Removes the edited and refreshed marks of a row that is removed.
+    fn forget(&mut self, row: &RowState<T>) {
+        if let RowState::Loaded(row) = row {
+            self.loaded -= 1;
+            self.dirty_rows.remove(row);
+            self.refreshed_rows.remove(row);
+        }
+    }
+
+    /// Sets the row to `Placeholder` and disposes its signal. Has to be followed by [`Self::compact`].
+    fn remove_and_dispose(&mut self, index: usize) {
+        let row = self.set(index, RowState::Placeholder);
+        self.forget(&row);
+        if let RowState::Loaded(row) = row {
+            row.dispose();
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&RowState<T>> {
+        let (start, run) = self.runs.range(..=index).next_back()?;
+        run.get(index - start)
+    }
+
+    fn iter_stored(&self) -> impl Iterator<Item = (usize, &RowState<T>)> + '_ {
+        self.runs
+            .iter()
+            .flat_map(|(start, run)| run.iter().enumerate().map(move |(i, row)| (start + i, row)))
+    }
+
+    /// Replaces the row at `index` and returns the previous one. Only keeps the count of loaded rows
+    /// up to date. Removing the marks of a replaced loaded row is up to the caller.
+    fn set(&mut self, index: usize, row: RowState<T>) -> RowState<T> {
+        if matches!(row, RowState::Loaded(_)) {
+            self.loaded += 1;
+        }
+
+        match self.get(index) {
+            Some(_) => {
+                let (start, run) = self.runs.range_mut(..=index).next_back().unwrap();
+                std::mem::replace(&mut run[index - start], row)
+            }
+            None if matches!(row, RowState::Placeholder) => RowState::Placeholder,
+            None => {
+                let (start, run) = self.join_runs(index..index + 1);
+                std::mem::replace(&mut run[index - start], row)
+            }
+        }
+    }
+
+    /// Writes the rows in `range` that are created by `row` and removes the marks of the rows
+    /// that are replaced.
+    fn write_range(&mut self, range: Range<usize>, mut row: impl FnMut(usize) -> RowState<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (start, run) = self.join_runs(range.clone());
+        let mut loaded = 0;
+        let replaced = range
+            .map(|index| {
+                let row = row(index);
+                if matches!(row, RowState::Loaded(_)) {
+                    loaded += 1;
+                }
+                std::mem::replace(&mut run[index - start], row)
+            })
+            .collect::<Vec<_>>();
+
+        self.loaded += loaded;
+        replaced.iter().for_each(|row| self.forget(row));
+    }
+
+    /// Joins the runs that overlap or touch `range` into one run that covers `range`. Rows of
+    /// `range` that are not stored yet are filled with placeholders.
+    fn join_runs(&mut self, range: Range<usize>) -> (usize, &mut Vec<RowState<T>>) {
+        let first_start = match self.runs.range(..=range.start).next_back() {
+            Some((start, run)) if start + run.len() >= range.start => *start,
+            _ => range.start,
+        };
+
+        let mut run = self.runs.remove(&first_start).unwrap_or_default();
+        let mut end = first_start + run.len();
+
+        // the joined runs are moved to the end of the first run which doesn't move its rows
+        while let Some((&start, _)) = self.runs.range(end..=range.end.max(end)).next() {
+            let next = self.runs.remove(&start).unwrap();
+            run.resize_with(start - first_start, || RowState::Placeholder);
+            run.extend(next);
+            end = first_start + run.len();
+        }
+
+        if end < range.end {
+            run.resize_with(range.end - first_start, || RowState::Placeholder);
+        }
+
+        (first_start, self.runs.entry(first_start).or_insert(run))
+    }
+
+    /// Removes the placeholders that have been set by [`Self::set`] by splitting the runs.
+    fn compact(&mut self) {
+        let has_placeholder =
+            |run: &Vec<RowState<T>>| run.iter().any(|row| matches!(row, RowState::Placeholder));
+
+        let starts = self
+            .runs
+            .iter()
+            .filter(|(_, run)| has_placeholder(run))
+            .map(|(start, _)| *start)
+            .collect::<Vec<_>>();
+
+        for start in starts {
+            let run = self.runs.remove(&start).unwrap();
+
+            let mut current_start = start;
+            let mut current = vec![];
+            for (index, row) in (start..).zip(run) {
+                if matches!(row, RowState::Placeholder) {
+                    if !current.is_empty() {
+                        self.runs
+                            .insert(current_start, std::mem::take(&mut current));
+                    }
+                    current_start = index + 1;
+                } else {
+                    current.push(row);
+                }
+            }
+            if !current.is_empty() {
+                self.runs.insert(current_start, current);
+            }
+        }
+    }
+
+    /// Counts the cache hits and misses for the rows in `range` that are about to be displayed.
+    /// Rows that were already part of the range of the previous call are not counted again.
+    pub fn record_access(&mut self, range: Range<usize>) {
+        let range = range.start.min(self.len)..range.end.min(self.len);
+        let accessed_range = std::mem::replace(&mut self.accessed_range, range.clone());
+
+        for index in range {
+            if accessed_range.contains(&index) {
+                continue;
+            }
+
+            match self[index] {
+                RowState::Loaded(_) => self.hits += 1,
+                RowState::Placeholder => self.misses += 1,
+                RowState::Loading | RowState::Error(_) => {}
+            }
+        }
+    }
+
+    /// Evicts loaded rows until at most `max_loaded_rows` rows are loaded. Rows furthest away from
+    /// `keep_range` are evicted first. Rows inside of `keep_range`, edited rows and rows for which
+    /// `is_pinned` returns `true` are never evicted.
+    pub fn evict(
+        &mut self,
+        keep_range: Range<usize>,
+        max_loaded_rows: usize,
+        is_pinned: impl Fn(usize) -> bool,
+    ) {
+        let excess = self.loaded.saturating_sub(max_loaded_rows);
+        if excess == 0 {
+            return;
+        }
+
+        let distance = |index: usize| {
+            if index < keep_range.start {
+                keep_range.start - index
+            } else {
+                index.saturating_sub(keep_range.end)
+            }
+        };
 
-        let slice = &self.rows[range.clone()];
+        let mut candidates = self
+            .iter_stored()
+            .filter(|(index, row)| {
+                matches!(row, RowState::Loaded(_))
+                    && !keep_range.contains(index)
+                    && !self.is_dirty(row)
+                    && !is_pinned(*index)
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
 
-        let start = slice.iter().position(do_load_predicate)?;
-        let end = slice.iter().rposition(do_load_predicate)?;
+        // only the `excess` furthest rows are needed so a partial selection is enough
+        if excess < candidates.len() {
+            candidates
+                .select_nth_unstable_by_key(excess, |index| std::cmp::Reverse(distance(*index)));
+            candidates.truncate(excess);
+        }
 
-        let start = start + range.start;
-        let end = end + range.start + 1;
+        for index in candidates {
+            self.remove_and_dispose(index);
+            self.evictions += 1;
+        }
+
+        self.compact();
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            loaded_rows: self.loaded,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    #[inline]
+    pub fn missing_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let is_missing = |index: &usize| self.get(*index).is_none();
+
+        let start = range.clone().find(is_missing)?;
+        let end = range.rev().find(is_missing)? + 1;
 
         Some(start..end)
     }
 
     #[inline]
     pub fn clear(&mut self) {
-        self.rows.fill(RowState::Placeholder);
+        self.runs.clear();
+        self.loaded = 0;
+        self.dirty_rows.clear();
+        self.refreshed_rows.clear();
+        self.accessed_range = 0..0;
     }
 }
 
+/// Deprecated: use [`LoadedRows::rows_in`] which returns placeholders for the rows that aren't stored.
+///
+/// Only the stored rows can be returned as a slice, so this panics if any row in the range is a
+/// `RowState::Placeholder`. Use [`LoadedRows::get_range`] to get a slice only if there is one.
 impl<T: Sync + Send> Index<Range<usize>> for LoadedRows<T> {
     type Output = [RowState<T>];
 
     #[inline]
     fn index(&self, index: Range<usize>) -> &Self::Output {
-        &self.rows[index]
+        self.get_range(index.clone())
+            .unwrap_or_else(|| panic!("rows {index:?} are not all stored"))
     }
 }
 
@@ -128,6 +525,325 @@ impl<T: Send + Sync> Index<usize> for LoadedRows<T> {
 
     #[inline]
     fn index(&self, index: usize) -> &Self::Output {
-        &self.rows[index]
+        self.get(index).unwrap_or(&self.placeholder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_window(loaded_rows: &mut LoadedRows<usize>, range: Range<usize>) {
+        let missing_range = loaded_rows.missing_range(range).unwrap();
+        loaded_rows.write_loading(missing_range.clone());
+        loaded_rows.write_loaded(
+            Ok((missing_range.clone().collect(), missing_range.clone())),
+            missing_range,
+        );
+    }
+
+    #[test]
+    fn storage_does_not_depend_on_row_count() {
+        let _owner = Owner::new();
+
+        let mut stored_rows = vec![];
+
+        for row_count in [1_000, 1_000_000, 100_000_000] {
+            let mut loaded_rows = LoadedRows::new();
+            loaded_rows.resize(row_count);
+            load_window(&mut loaded_rows, row_count - 500..row_count);
+
+            assert_eq!(loaded_rows.len(), row_count);
+            assert!(matches!(loaded_rows[0], RowState::Placeholder));
+            assert!(matches!(loaded_rows[row_count - 1], RowState::Loaded(_)));
+
+            stored_rows.push(loaded_rows.iter_stored().count());
+        }
+
+        assert_eq!(stored_rows, vec![500, 500, 500]);
+    }
+
+    #[test]
+    fn missing_range_spans_first_to_last_placeholder() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 20..30);
+
+        assert_eq!(loaded_rows.missing_range(20..30), None);
+        assert_eq!(loaded_rows.missing_range(10..40), Some(10..40));
+        assert_eq!(loaded_rows.missing_range(20..40), Some(30..40));
+        assert_eq!(loaded_rows.missing_range(0..25), Some(0..20));
+    }
+
+    #[test]
+    fn refreshed_rows_are_updated_in_place() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..30);
+        let RowState::Loaded(edited_row) = loaded_rows[15] else {
+            unreachable!()
+        };
+        loaded_rows.mark_dirty(edited_row);
+
+        loaded_rows.retain_range(10..20);
+        assert_eq!(loaded_rows.metrics().loaded_rows, 10);
+
+        let updates = loaded_rows.write_refreshed((100..110).collect(), 10..20);
+
+        // the edited row is kept
+        assert_eq!(updates.len(), 9);
+        for (row, new_row) in updates {
+            row.set(new_row);
+        }
+
+        assert!(matches!(loaded_rows[10], RowState::Loaded(row) if row.get_untracked() == 100));
+        assert!(matches!(loaded_rows[15], RowState::Loaded(row) if row.get_untracked() == 15));
+        let RowState::Loaded(refreshed_row) = loaded_rows[10] else {
+            unreachable!()
+        };
+        assert!(loaded_rows.take_refreshed(refreshed_row));
+        assert!(!loaded_rows.take_refreshed(refreshed_row));
+    }
+
+    #[test]
+    fn loading_rows_can_be_reset_to_placeholders() {
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.write_loading(0..10);
+        loaded_rows.write_placeholder(5..15);
+
+        assert_eq!(loaded_rows.len(), 10);
+        assert_eq!(loaded_rows.missing_range(0..10), Some(5..10));
+    }
+
+    fn loaded_signal(loaded_rows: &LoadedRows<usize>, index: usize) -> RwSignal<usize> {
+        match loaded_rows[index] {
+            RowState::Loaded(row) => row,
+            ref state => panic!("row {index} is {state:?}"),
+        }
+    }
+
+    fn loaded_indices(loaded_rows: &LoadedRows<usize>) -> Vec<usize> {
+        loaded_rows.iter_loaded().map(|(index, _)| index).collect()
+    }
+
+    #[test]
+    fn evicts_furthest_rows_first() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..100);
+
+        loaded_rows.evict(45..55, 30, |_| false);
+
+        assert_eq!(loaded_rows.metrics().loaded_rows, 30);
+        assert_eq!(loaded_rows.metrics().evictions, 70);
+        assert_eq!(loaded_indices(&loaded_rows), (35..65).collect::<Vec<_>>());
+
+        // nothing to do within the budget
+        loaded_rows.evict(45..55, 30, |_| false);
+        assert_eq!(loaded_rows.metrics().evictions, 70);
+    }
+
+    #[test]
+    fn never_evicts_kept_pinned_or_edited_rows() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..100);
+        loaded_rows.mark_dirty(loaded_signal(&loaded_rows, 99));
+
+        loaded_rows.evict(10..20, 0, |index| index == 0);
+
+        let mut expected = vec![0];
+        expected.extend(10..20);
+        expected.push(99);
+        assert_eq!(loaded_indices(&loaded_rows), expected);
+    }
+
+    #[test]
+    fn edited_mark_stays_with_the_row() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..100);
+        loaded_rows.mark_dirty(loaded_signal(&loaded_rows, 50));
+
+        // another row is loaded at the index of the edited row, for example after a reload
+        loaded_rows.invalidate(50..51);
+        load_window(&mut loaded_rows, 50..51);
+
+        loaded_rows.evict(0..10, 10, |_| false);
+        assert_eq!(loaded_indices(&loaded_rows), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn metrics_count_hits_and_misses_once_per_row() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..10);
+
+        loaded_rows.record_access(5..15);
+        loaded_rows.record_access(5..15);
+        loaded_rows.record_access(10..20);
+
+        let metrics = loaded_rows.metrics();
+        assert_eq!(metrics.hits, 5);
+        assert_eq!(metrics.misses, 10);
+        assert_eq!(metrics.loaded_rows, 10);
+        assert_eq!(metrics.evictions, 0);
+    }
+
+    #[test]
+    fn range_index_returns_stored_rows_as_slice() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 10..20);
+        load_window(&mut loaded_rows, 20..30);
+        loaded_rows.write_loading(30..40);
+
+        let rows = &loaded_rows[15..35];
+        assert_eq!(rows.len(), 20);
+        assert!(matches!(rows[0], RowState::Loaded(row) if row.get_untracked() == 15));
+        assert!(matches!(rows[19], RowState::Loading));
+        assert!(loaded_rows[50..50].is_empty());
+
+        assert!(loaded_rows.get_range(5..15).is_none());
+        assert!(loaded_rows.get_range(35..45).is_none());
+
+        loaded_rows.invalidate(25..26);
+        assert!(loaded_rows.get_range(20..30).is_none());
+        assert_eq!(loaded_rows.get_range(26..40).map(<[_]>::len), Some(14));
+    }
+
+    #[test]
+    fn rows_in_returns_placeholders_for_gaps() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 10..20);
+        loaded_rows.write_loading(30..40);
+
+        let rows = loaded_rows.rows_in(5..35).collect::<Vec<_>>();
+        assert_eq!(rows.len(), 30);
+        assert!(matches!(rows[0], RowState::Placeholder));
+        assert!(matches!(rows[5], RowState::Loaded(row) if row.get_untracked() == 10));
+        assert!(matches!(rows[15], RowState::Placeholder));
+        assert!(matches!(rows[25], RowState::Loading));
+
+        assert_eq!(loaded_rows.rows_in(95..120).count(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "not all stored")]
+    fn range_index_panics_for_placeholders() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 10..20);
+
+        let _ = &loaded_rows[0..20];
+    }
+
+    #[test]
+    fn loaded_count_is_kept_up_to_date() {
+        let _owner = Owner::new();
+
+        let mut loaded_rows = LoadedRows::<usize>::new();
+        let assert_loaded_count = |loaded_rows: &LoadedRows<usize>| {
+            assert_eq!(
+                loaded_rows.metrics().loaded_rows,
+                loaded_rows.iter_loaded().count()
+            );
+        };
+
+        loaded_rows.resize(100);
+        load_window(&mut loaded_rows, 0..50);
+        assert_loaded_count(&loaded_rows);
+
+        loaded_rows.write_loading(40..60);
+        loaded_rows.write_loaded(Err("error".to_string()), 55..60);
+        assert_loaded_count(&loaded_rows);
+
+        loaded_rows.write_refreshed((0..60).collect(), 0..60);
+        assert_loaded_count(&loaded_rows);
+
+        loaded_rows.invalidate(10..20);
+        loaded_rows.evict(0..5, 10, |_| false);
+        assert_loaded_count(&loaded_rows);
+
+        loaded_rows.retain_range(0..3);
+        loaded_rows.resize(2);
+        assert_loaded_count(&loaded_rows);
+        assert_eq!(loaded_rows.metrics().loaded_rows, 2);
+
+        loaded_rows.clear();
+        assert_loaded_count(&loaded_rows);
+    }
+
+    /// Scrolls through the whole table with eviction enabled like the table does and reports the
+    /// time per scroll step and the number of stored rows.
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture bench_`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_scrolling_with_eviction() {
+        const WINDOW: usize = 100;
+        const MAX_LOADED_ROWS: usize = 1_000;
+        const STEPS: usize = 10_000;
+
+        let _owner = Owner::new();
+
+        let mut max_stored_rows = vec![];
+
+        for row_count in [100_000, 10_000_000, 1_000_000_000] {
+            let mut loaded_rows = LoadedRows::new();
+            loaded_rows.resize(row_count);
+
+            let step = row_count / STEPS;
+            let mut max_stored = 0;
+            let started = std::time::Instant::now();
+
+            for start in (0..row_count - WINDOW).step_by(step).take(STEPS) {
+                let range = start..start + WINDOW;
+
+                loaded_rows.evict(range.clone(), MAX_LOADED_ROWS, |_| false);
+                loaded_rows.record_access(range.clone());
+                if let Some(missing_range) = loaded_rows.missing_range(range.clone()) {
+                    loaded_rows.write_loading(missing_range.clone());
+                    loaded_rows.write_loaded(
+                        Ok((missing_range.clone().collect(), missing_range.clone())),
+                        missing_range,
+                    );
+                }
+                let _ = &loaded_rows[range];
+
+                max_stored = max_stored.max(loaded_rows.iter_stored().count());
+            }
+
+            println!(
+                "{row_count:>13} rows: {:>8.2?} per scroll step, at most {max_stored} rows stored",
+                started.elapsed() / STEPS as u32,
+            );
+            max_stored_rows.push(max_stored);
+        }
+
+        assert!(
+            max_stored_rows
+                .iter()
+                .all(|stored| *stored <= MAX_LOADED_ROWS + WINDOW)
+        );
     }
 }
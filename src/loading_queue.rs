@@ -0,0 +1,326 @@
+use crate::AbortToken;
+use std::ops::Range;
+
+/// Queue of chunks that still have to be loaded. Used by [`TableContent`] internally to limit
+/// the number of concurrent requests to the data provider.
+///
+/// Chunks are handed out nearest to the viewport first. Chunks that are no longer inside of the
+/// range the table wants to display are dropped before they are even started. Chunks that are
+/// already loading are aborted in that case.
+pub struct LoadingQueue {
+    pending: Vec<(Range<usize>, ChunkKind)>,
+    in_flight: Vec<(Range<usize>, AbortToken)>,
+    wanted_range: Range<usize>,
+    viewport: Range<usize>,
+    running: usize,
+    max_running: usize,
+}
+
+/// What happens with the rows of a chunk once they are loaded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChunkKind {
+    /// The rows are missing and written as loaded rows.
+    Load,
+    /// The rows are loaded already and are updated in place by a soft reload.
+    Refresh,
+}
+
+impl LoadingQueue {
+    /// Creates a new queue that allows at most `max_running` chunks to be loaded at the same time.
+    /// `None` means unlimited.
+    pub fn new(max_running: Option<usize>) -> Self {
+        Self {
+            pending: vec![],
+            in_flight: vec![],
+            wanted_range: 0..0,
+            viewport: 0..0,
+            running: 0,
+            max_running: max_running.unwrap_or(usize::MAX).max(1),
+        }
+    }
+
+    /// Updates the range of rows that the table wants to have loaded and the range of rows
+    /// that is currently visible.
+    pub fn set_ranges(&mut self, wanted_range: Range<usize>, viewport: Range<usize>) {
+        self.wanted_range = wanted_range;
+        self.viewport = viewport;
+    }
+
+    /// Adds the given chunks to the queue.
+    pub fn push(&mut self, ranges: impl IntoIterator<Item = Range<usize>>, kind: ChunkKind) {
+        self.pending
+            .extend(ranges.into_iter().map(|range| (range, kind)));
+    }
+
+    /// Removes all pending chunks and aborts all chunks that are currently loading.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+
+        for (_, abort_token) in self.in_flight.drain(..) {
+            abort_token.abort();
+        }
+    }
+
+    /// Removes all pending chunks and aborts all loading chunks that don't overlap with the
+    /// wanted range anymore. Returns the ranges of all of them.
+    pub fn drop_unwanted(&mut self) -> Vec<Range<usize>> {
+        let wanted_range = self.wanted_range.clone();
+        let is_wanted =
+            |range: &Range<usize>| range.start < wanted_range.end && wanted_range.start < range.end;
+
+        let (keep, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(range, _)| is_wanted(range));
+        self.pending = keep;
+        let mut dropped = dropped
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect::<Vec<_>>();
+
+        let (keep, aborted): (Vec<_>, Vec<_>) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition(|(range, _)| is_wanted(range));
+        self.in_flight = keep;
+
+        for (range, abort_token) in aborted {
+            abort_token.abort();
+            dropped.push(range);
+        }
+
+        dropped
+    }
+
+    /// Removes all pending chunks and aborts all loading chunks that overlap with one of `ranges`.
+    /// Returns the ranges of all of them.
+    pub fn abort_overlapping(&mut self, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+        let overlaps = |range: &Range<usize>| {
+            ranges
+                .iter()
+                .any(|other| range.start < other.end && other.start < range.end)
+        };
+
+        let (dropped, keep): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(range, _)| overlaps(range));
+        self.pending = keep;
+        let mut dropped = dropped
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect::<Vec<_>>();
+
+        let (aborted, keep): (Vec<_>, Vec<_>) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition(|(range, _)| overlaps(range));
+        self.in_flight = keep;
+
+        for (range, abort_token) in aborted {
+            abort_token.abort();
+            dropped.push(range);
+        }
+
+        dropped
+    }
+
+    /// Returns how many more loaders may be started right now.
+    pub fn available_slots(&self) -> usize {
+        self.max_running
+            .saturating_sub(self.running)
+            .min(self.pending.len())
+    }
+
+    /// Registers a new loader. Has to be matched by a call to [`Self::finish_loader`].
+    pub fn start_loader(&mut self) {
+        self.running += 1;
+    }
+
+    /// Unregisters a loader that has been started with [`Self::start_loader`].
+    pub fn finish_loader(&mut self) {
+        self.running = self.running.saturating_sub(1);
+    }
+
+    /// Marks the chunk as loading and returns the token that is aborted if it isn't needed anymore.
+    pub fn start_chunk(&mut self, range: Range<usize>) -> AbortToken {
+        let abort_token = AbortToken::new();
+        self.in_flight.push((range, abort_token.clone()));
+        abort_token
+    }
+
+    /// Marks the chunk that has been started with [`Self::start_chunk`] as done.
+    pub fn finish_chunk(&mut self, abort_token: &AbortToken) {
+        self.in_flight
+            .retain(|(_, in_flight_token)| !in_flight_token.ptr_eq(abort_token));
+    }
+
+    /// Takes the pending chunk that is nearest to the viewport out of the queue.
+    pub fn pop_nearest(&mut self) -> Option<(Range<usize>, ChunkKind)> {
+        let (index, _) = self
+            .pending
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (range, _))| distance(range, &self.viewport))?;
+
+        Some(self.pending.remove(index))
+    }
+}
+
+/// Number of rows between the two ranges. `0` if they overlap.
+fn distance(range: &Range<usize>, other: &Range<usize>) -> usize {
+    if range.end <= other.start {
+        other.start - range.end
+    } else {
+        range.start.saturating_sub(other.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled_queue(max_running: Option<usize>) -> LoadingQueue {
+        let mut queue = LoadingQueue::new(max_running);
+        queue.set_ranges(0..1000, 400..500);
+        queue.push(
+            [0..100, 300..400, 900..1000, 500..600, 100..200],
+            ChunkKind::Load,
+        );
+        queue
+    }
+
+    #[test]
+    fn distance_between_ranges() {
+        assert_eq!(distance(&(0..100), &(400..500)), 300);
+        assert_eq!(distance(&(600..700), &(400..500)), 100);
+        assert_eq!(distance(&(300..400), &(400..500)), 0);
+        assert_eq!(distance(&(500..600), &(400..500)), 0);
+        assert_eq!(distance(&(450..460), &(400..500)), 0);
+    }
+
+    #[test]
+    fn pop_nearest_to_viewport_first() {
+        let mut queue = filled_queue(None);
+
+        let mut popped = vec![];
+        while let Some((range, _)) = queue.pop_nearest() {
+            popped.push(range);
+        }
+
+        assert_eq!(
+            popped,
+            vec![300..400, 500..600, 100..200, 0..100, 900..1000]
+        );
+        assert_eq!(queue.pop_nearest(), None);
+    }
+
+    #[test]
+    fn pop_nearest_follows_viewport() {
+        let mut queue = filled_queue(None);
+        queue.set_ranges(0..1000, 950..1000);
+
+        assert_eq!(queue.pop_nearest(), Some((900..1000, ChunkKind::Load)));
+        assert_eq!(queue.pop_nearest(), Some((500..600, ChunkKind::Load)));
+    }
+
+    #[test]
+    fn drop_unwanted_removes_pending_outside_of_wanted_range() {
+        let mut queue = filled_queue(None);
+        queue.set_ranges(250..650, 400..500);
+
+        let mut dropped = queue.drop_unwanted();
+        dropped.sort_by_key(|range| range.start);
+
+        assert_eq!(dropped, vec![0..100, 100..200, 900..1000]);
+        assert_eq!(queue.pop_nearest(), Some((300..400, ChunkKind::Load)));
+        assert_eq!(queue.pop_nearest(), Some((500..600, ChunkKind::Load)));
+        assert_eq!(queue.pop_nearest(), None);
+    }
+
+    #[test]
+    fn available_slots_respect_max_running() {
+        let mut queue = filled_queue(Some(2));
+        assert_eq!(queue.available_slots(), 2);
+
+        queue.start_loader();
+        assert_eq!(queue.available_slots(), 1);
+        queue.start_loader();
+        assert_eq!(queue.available_slots(), 0);
+
+        queue.finish_loader();
+        assert_eq!(queue.available_slots(), 1);
+
+        let mut unlimited = filled_queue(None);
+        unlimited.start_loader();
+        assert_eq!(unlimited.available_slots(), 5);
+    }
+
+    #[test]
+    fn drop_unwanted_aborts_loading_chunks_outside_of_wanted_range() {
+        let mut queue = LoadingQueue::new(None);
+        queue.set_ranges(0..200, 0..100);
+
+        let kept = queue.start_chunk(100..200);
+        let aborted = queue.start_chunk(300..400);
+
+        assert_eq!(queue.drop_unwanted(), vec![300..400]);
+        assert!(aborted.is_aborted());
+        assert!(!kept.is_aborted());
+
+        // finished chunks can't be aborted anymore
+        queue.finish_chunk(&kept);
+        queue.set_ranges(500..600, 500..600);
+        assert!(queue.drop_unwanted().is_empty());
+        assert!(!kept.is_aborted());
+    }
+
+    #[test]
+    fn clear_aborts_all_loading_chunks() {
+        let mut queue = filled_queue(None);
+
+        let first = queue.start_chunk(0..100);
+        let second = queue.start_chunk(100..200);
+        queue.clear();
+
+        assert!(first.is_aborted());
+        assert!(second.is_aborted());
+        assert_eq!(queue.pop_nearest(), None);
+    }
+
+    #[test]
+    fn refresh_chunks_keep_their_kind() {
+        let mut queue = filled_queue(None);
+        queue.push([420..480, 950..1000], ChunkKind::Refresh);
+        queue.set_ranges(0..1000, 430..470);
+
+        assert_eq!(queue.pop_nearest(), Some((420..480, ChunkKind::Refresh)));
+        assert_eq!(queue.pop_nearest(), Some((300..400, ChunkKind::Load)));
+
+        queue.set_ranges(0..900, 400..500);
+        let mut dropped = queue.drop_unwanted();
+        dropped.sort_by_key(|range| range.start);
+        assert_eq!(dropped, vec![900..1000, 950..1000]);
+    }
+
+    #[test]
+    fn abort_overlapping_removes_pending_and_aborts_loading_chunks() {
+        let mut queue = filled_queue(None);
+        let loading = queue.pop_nearest().unwrap().0;
+        let loading_token = queue.start_chunk(loading.clone());
+        let other = queue.pop_nearest().unwrap().0;
+        let other_token = queue.start_chunk(other.clone());
+
+        assert_eq!(loading, 300..400);
+        assert_eq!(other, 500..600);
+
+        let mut aborted = queue.abort_overlapping(&[350..351, 950..960]);
+        aborted.sort_by_key(|range| range.start);
+
+        assert_eq!(aborted, vec![300..400, 900..1000]);
+        assert!(loading_token.is_aborted());
+        assert!(!other_token.is_aborted());
+
+        let mut pending = vec![];
+        while let Some((range, _)) = queue.pop_nearest() {
+            pending.push(range);
+        }
+        assert_eq!(pending, vec![100..200, 0..100]);
+    }
+}
@@ -0,0 +1,692 @@
+use crate::NumberRenderOptions;
+use std::fmt::Display;
+
+/// How numbers are written in a language or region. Provide it via context to use it for all
+/// tables of an app or set it for a single column with `#[table(format(locale = "de"))]`.
+/// The context is read once when a table is created.
+///
+/// Without a locale [`NumberLocale::EN`] is used which formats numbers like Rust does.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn App() -> impl IntoView {
+///     provide_context(NumberLocale {
+///         use_grouping: true,
+///         ..NumberLocale::DE
+///     });
+///
+///     // all tables in here show 1234.5 as "1.234,5"
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NumberLocale {
+    /// Separates the integer from the fractional digits.
+    pub decimal_separator: char,
+    /// Separates the groups of three integer digits.
+    pub thousands_separator: char,
+    /// Whether the `thousands_separator` is used if `NumberRenderOptions::thousands_separator` isn't given.
+    pub use_grouping: bool,
+    /// Appended to percentages.
+    pub percent_suffix: &'static str,
+    /// Appended to numbers in compact notation for thousands, millions, billions and trillions.
+    pub compact_suffixes: [&'static str; 4],
+    /// Whether the currency symbol is written before or after the number.
+    pub currency_placement: CurrencyPlacement,
+    /// Between the currency symbol and the number. Currency codes like "CHF" are always separated by a space.
+    pub currency_spacing: &'static str,
+}
+
+impl NumberLocale {
+    pub const EN: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: ',',
+        use_grouping: false,
+        percent_suffix: "%",
+        compact_suffixes: ["k", "M", "B", "T"],
+        currency_placement: CurrencyPlacement::Before,
+        currency_spacing: "",
+    };
+
+    pub const DE: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: '.',
+        use_grouping: false,
+        percent_suffix: "\u{a0}%",
+        compact_suffixes: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+        currency_placement: CurrencyPlacement::After,
+        currency_spacing: "\u{a0}",
+    };
+
+    pub const DE_CH: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: '’',
+        use_grouping: false,
+        percent_suffix: "%",
+        compact_suffixes: ["\u{a0}Tsd.", "\u{a0}Mio.", "\u{a0}Mrd.", "\u{a0}Bio."],
+        currency_placement: CurrencyPlacement::Before,
+        currency_spacing: "\u{a0}",
+    };
+
+    pub const FR: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: '\u{202f}',
+        use_grouping: false,
+        percent_suffix: "\u{a0}%",
+        compact_suffixes: ["\u{a0}k", "\u{a0}M", "\u{a0}Md", "\u{a0}Bn"],
+        currency_placement: CurrencyPlacement::After,
+        currency_spacing: "\u{a0}",
+    };
+
+    pub const ES: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: '.',
+        use_grouping: false,
+        percent_suffix: "\u{a0}%",
+        compact_suffixes: ["\u{a0}mil", "\u{a0}M", "\u{a0}mil\u{a0}M", "\u{a0}B"],
+        currency_placement: CurrencyPlacement::After,
+        currency_spacing: "\u{a0}",
+    };
+
+    /// Returns the predefined locale for a language tag like `"de"` or `"de-CH"`.
+    /// If there is none for the region, the one for the language is returned.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag.to_ascii_lowercase().replace('_', "-");
+
+        match tag.as_str() {
+            "de-ch" | "de-li" => return Some(Self::DE_CH),
+            _ => {}
+        }
+
+        match tag.split('-').next().unwrap_or_default() {
+            "en" => Some(Self::EN),
+            "de" => Some(Self::DE),
+            "fr" => Some(Self::FR),
+            "es" => Some(Self::ES),
+            _ => None,
+        }
+    }
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::EN
+    }
+}
+
+/// Uses [`NumberLocale::from_tag`] and falls back to [`NumberLocale::EN`] for unknown tags.
+impl From<&str> for NumberLocale {
+    fn from(tag: &str) -> Self {
+        Self::from_tag(tag).unwrap_or_else(|| {
+            leptos::logging::warn!("Unknown number locale \"{tag}\", falling back to EN");
+            Self::default()
+        })
+    }
+}
+
+/// Where the currency symbol is written.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CurrencyPlacement {
+    /// Like "€1.50".
+    #[default]
+    Before,
+    /// Like "1,50 €".
+    After,
+}
+
+/// When to show the sign of a number.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SignDisplay {
+    /// Only negative numbers have a sign.
+    #[default]
+    Auto,
+    /// Positive numbers and zero have a `+` sign.
+    Always,
+    /// Positive numbers have a `+` sign, zero has no sign.
+    ExceptZero,
+    /// No sign is shown.
+    Never,
+}
+
+/// Used by `#[table(format(sign_display = "always"))]`. Accepts `"auto"`, `"always"`, `"except_zero"` and `"never"`
+/// and falls back to [`SignDisplay::Auto`] for unknown values.
+impl From<&str> for SignDisplay {
+    fn from(value: &str) -> Self {
+        match value {
+            "auto" => Self::Auto,
+            "always" => Self::Always,
+            "except_zero" => Self::ExceptZero,
+            "never" => Self::Never,
+            _ => {
+                leptos::logging::warn!("Unknown sign display \"{value}\", falling back to auto");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// How large numbers are written.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// All integer digits are shown.
+    #[default]
+    Standard,
+    /// Thousands, millions etc. are abbreviated like "1.2k" with the suffixes of the [`NumberLocale`].
+    Compact,
+}
+
+/// Used by `#[table(format(notation = "compact"))]`. Accepts `"standard"` and `"compact"` and falls back to
+/// [`Notation::Standard`] for unknown values.
+impl From<&str> for Notation {
+    fn from(value: &str) -> Self {
+        match value {
+            "standard" => Self::Standard,
+            "compact" => Self::Compact,
+            _ => {
+                leptos::logging::warn!("Unknown notation \"{value}\", falling back to standard");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// Formats a number according to `options`. `value` has to display the number without an exponent
+/// like all the number types of the standard library and `Decimal` do.
+pub(crate) fn format_number(
+    value: &dyn Display,
+    integer: bool,
+    options: &NumberRenderOptions,
+) -> String {
+    let locale = options.locale.unwrap_or_default();
+    let percent = options.percent.unwrap_or_default();
+    let compact = options.notation == Some(Notation::Compact);
+
+    // Integers have no fraction, so like in Rust's formatting the precision doesn't pad them
+    // with zeros. Only the compact notation scales them down to numbers with a fraction.
+    let precision = match options.precision {
+        Some(_) if integer && !compact => Some(0),
+        precision => precision,
+    };
+
+    // currencies have a fixed number of minor units like 2 for cents
+    let precision = match options.currency {
+        Some(currency) if options.significant_digits.is_none() && !compact => {
+            precision.or(Some(currency_digits(currency)))
+        }
+        _ => precision,
+    };
+
+    // Keep the rounding of Rust's formatting if only the precision is given.
+    let plain = match precision {
+        Some(precision) if !percent && !compact && options.significant_digits.is_none() => {
+            format!("{value:.precision$}")
+        }
+        _ => value.to_string(),
+    };
+
+    // NaN and infinity
+    let Some(mut number) = Digits::parse(&plain) else {
+        return plain;
+    };
+
+    if percent {
+        number.point += 2;
+    }
+
+    let mut compact_suffix = "";
+
+    if compact {
+        let mut unit = 0;
+
+        loop {
+            let mut scaled = number.clone();
+            let int_digits = scaled.significant_int_digits();
+            let scale = (int_digits.saturating_sub(1) / 3).max(unit).min(4);
+            scaled.point -= 3 * scale as isize;
+
+            match (precision, options.significant_digits) {
+                (Some(precision), _) => scaled.round_fraction(precision),
+                (None, Some(digits)) => scaled.round_significant(digits),
+                // like "1.2k", "12k" and "123k"
+                (None, None) => scaled.round_significant(scaled.significant_int_digits().max(2)),
+            }
+
+            // rounding can carry over to the next unit like 999.96k to 1000k
+            if scale < 4 && scaled.significant_int_digits() > 3 {
+                unit = scale + 1;
+                continue;
+            }
+
+            if scale > 0 {
+                compact_suffix = locale.compact_suffixes[scale - 1];
+            }
+            number = scaled;
+            break;
+        }
+    } else {
+        match (precision, options.significant_digits) {
+            (_, Some(digits)) => number.round_significant(digits),
+            (Some(precision), None) => number.round_fraction(precision),
+            (None, None) => {}
+        }
+    }
+
+    let sign = match options.sign_display.unwrap_or_default() {
+        SignDisplay::Auto if number.negative => "-",
+        SignDisplay::Always | SignDisplay::ExceptZero if number.negative => "-",
+        SignDisplay::Always => "+",
+        SignDisplay::ExceptZero if !number.is_zero() => "+",
+        _ => "",
+    };
+
+    let thousands_separator = options
+        .thousands_separator
+        .unwrap_or(locale.use_grouping)
+        .then_some(locale.thousands_separator);
+    let decimal_separator = options
+        .decimal_separator
+        .unwrap_or(locale.decimal_separator);
+
+    let (currency_prefix, currency_suffix) = options
+        .currency
+        .map(|currency| currency_affixes(currency, &locale))
+        .unwrap_or_default();
+
+    let mut text = sign.to_string();
+    text.push_str(&currency_prefix);
+    number.write(&mut text, thousands_separator, decimal_separator);
+    text.push_str(compact_suffix);
+    if percent {
+        text.push_str(locale.percent_suffix);
+    }
+    text.push_str(&currency_suffix);
+    if let Some(unit) = options.unit {
+        text.push('\u{a0}');
+        text.push_str(unit);
+    }
+
+    text
+}
+
+/// The symbol of an ISO 4217 currency code like "EUR". Other codes and symbols are returned as they are.
+pub(crate) fn currency_symbol(currency: &str) -> &str {
+    match currency {
+        "EUR" => "€",
+        "USD" => "$",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "CNY" => "CN¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        "BRL" => "R$",
+        _ => currency,
+    }
+}
+
+/// The number of fractional digits that are shown for a currency by default.
+pub(crate) fn currency_digits(currency: &str) -> usize {
+    match currency {
+        "JPY" | "KRW" | "ISK" | "CLP" | "VND" => 0,
+        _ => 2,
+    }
+}
+
+/// The text before and after the number for the `currency` according to the `locale`.
+pub(crate) fn currency_affixes(currency: &str, locale: &NumberLocale) -> (String, String) {
+    let symbol = currency_symbol(currency);
+    let spacing = if locale.currency_spacing.is_empty() && symbol.chars().all(char::is_alphabetic) {
+        "\u{a0}"
+    } else {
+        locale.currency_spacing
+    };
+
+    match locale.currency_placement {
+        CurrencyPlacement::Before => (format!("{symbol}{spacing}"), String::new()),
+        CurrencyPlacement::After => (String::new(), format!("{spacing}{symbol}")),
+    }
+}
+
+/// A decimal number as its digits and the position of the decimal point.
+#[derive(Clone, Debug, PartialEq)]
+struct Digits {
+    negative: bool,
+    digits: Vec<u8>,
+    /// The number of digits before the decimal point. Can be negative or greater than the number of digits.
+    point: isize,
+}
+
+impl Digits {
+    fn parse(plain: &str) -> Option<Self> {
+        let (negative, unsigned) = match plain.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, plain.strip_prefix('+').unwrap_or(plain)),
+        };
+        let (int, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        if int.is_empty()
+            || !int
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        Some(Self {
+            negative,
+            digits: int
+                .bytes()
+                .chain(fraction.bytes())
+                .map(|b| b - b'0')
+                .collect(),
+            point: int.len() as isize,
+        })
+    }
+
+    fn digit(&self, index: isize) -> u8 {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.digits.get(index).copied())
+            .unwrap_or(0)
+    }
+
+    fn first_non_zero(&self) -> Option<isize> {
+        self.digits
+            .iter()
+            .position(|digit| *digit != 0)
+            .map(|index| index as isize)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.first_non_zero().is_none()
+    }
+
+    /// The number of integer digits without leading zeros.
+    fn significant_int_digits(&self) -> usize {
+        self.first_non_zero()
+            .map_or(0, |first| (self.point - first).max(0) as usize)
+    }
+
+    /// Rounds half up so that exactly `fraction_digits` digits are left after the decimal point.
+    fn round_fraction(&mut self, fraction_digits: usize) {
+        self.round_at(self.point + fraction_digits as isize);
+
+        let len = (self.point + fraction_digits as isize).max(0) as usize;
+        if self.digits.len() < len {
+            self.digits.resize(len, 0);
+        }
+    }
+
+    /// Rounds half up to the given number of significant digits and removes trailing zeros of the fraction.
+    fn round_significant(&mut self, significant_digits: usize) {
+        let Some(first) = self.first_non_zero() else {
+            return;
+        };
+
+        self.round_at(first + significant_digits.max(1) as isize);
+
+        let int_len = self.point.max(0) as usize;
+        while self.digits.len() > int_len && self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    /// Keeps the first `keep` digits and rounds half up. Dropped integer digits become zeros.
+    fn round_at(&mut self, keep: isize) {
+        if keep >= self.digits.len() as isize {
+            return;
+        }
+
+        let round_up = self.digit(keep) >= 5;
+        let int_len = self.point.max(0) as usize;
+        let keep = keep.max(0) as usize;
+
+        self.digits.truncate(keep);
+        if self.digits.len() < int_len {
+            self.digits.resize(int_len, 0);
+        }
+
+        if round_up {
+            let mut index = keep;
+            loop {
+                if index == 0 {
+                    // all kept digits were 9 (or none were kept)
+                    self.digits.insert(0, 1);
+                    self.point += 1;
+                    break;
+                }
+                index -= 1;
+                if self.digits[index] == 9 {
+                    self.digits[index] = 0;
+                } else {
+                    self.digits[index] += 1;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn write(&self, out: &mut String, thousands_separator: Option<char>, decimal_separator: char) {
+        let int_digits = (0..self.point.max(0))
+            .map(|index| self.digit(index))
+            .skip_while(|digit| *digit == 0)
+            .collect::<Vec<_>>();
+
+        if int_digits.is_empty() {
+            out.push('0');
+        }
+        for (i, digit) in int_digits.iter().enumerate() {
+            if i > 0
+                && (int_digits.len() - i) % 3 == 0
+                && let Some(separator) = thousands_separator
+            {
+                out.push(separator);
+            }
+            out.push((b'0' + digit) as char);
+        }
+
+        let fraction_len = self.digits.len() as isize - self.point;
+        if fraction_len > 0 {
+            out.push(decimal_separator);
+            for index in self.point..self.digits.len() as isize {
+                out.push((b'0' + self.digit(index)) as char);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(value: f64, options: NumberRenderOptions) -> String {
+        format_number(&value, false, &options)
+    }
+
+    #[test]
+    fn keeps_rust_formatting_by_default() {
+        assert_eq!(format(1234.5, NumberRenderOptions::default()), "1234.5");
+        assert_eq!(
+            format(
+                -0.001,
+                NumberRenderOptions {
+                    precision: Some(2),
+                    ..Default::default()
+                }
+            ),
+            "-0.00"
+        );
+        assert_eq!(format(f64::NAN, NumberRenderOptions::default()), "NaN");
+    }
+
+    #[test]
+    fn locale_separators_and_sign() {
+        let options = NumberRenderOptions {
+            precision: Some(2),
+            thousands_separator: Some(true),
+            sign_display: Some(SignDisplay::Always),
+            locale: Some(NumberLocale::DE),
+            ..Default::default()
+        };
+
+        assert_eq!(format(1234567.891, options), "+1.234.567,89");
+        assert_eq!(format(-999.999, options), "-1.000,00");
+        assert_eq!(
+            format(
+                0.0,
+                NumberRenderOptions {
+                    sign_display: Some(SignDisplay::ExceptZero),
+                    ..options
+                }
+            ),
+            "0,00"
+        );
+    }
+
+    #[test]
+    fn unknown_format_values_fall_back_to_the_default() {
+        assert_eq!(SignDisplay::from("except_zero"), SignDisplay::ExceptZero);
+        assert_eq!(SignDisplay::from("exceptZero"), SignDisplay::Auto);
+        assert_eq!(Notation::from("compact"), Notation::Compact);
+        assert_eq!(Notation::from("scientific"), Notation::Standard);
+        assert_eq!(NumberLocale::from("de-AT"), NumberLocale::DE);
+        assert_eq!(NumberLocale::from("xx"), NumberLocale::EN);
+    }
+
+    #[test]
+    fn significant_digits_and_percent() {
+        let significant = NumberRenderOptions {
+            significant_digits: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(format(123456.0, significant), "123000");
+        assert_eq!(format(0.0012345, significant), "0.00123");
+        assert_eq!(format(1.5, significant), "1.5");
+
+        let percent = NumberRenderOptions {
+            percent: Some(true),
+            precision: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(format(0.1234, percent), "12.3%");
+        assert_eq!(
+            format(
+                0.5,
+                NumberRenderOptions {
+                    locale: Some(NumberLocale::FR),
+                    ..percent
+                }
+            ),
+            "50,0\u{a0}%"
+        );
+    }
+
+    #[test]
+    fn integers_are_not_padded_by_the_precision() {
+        let options = NumberRenderOptions {
+            precision: Some(2),
+            ..Default::default()
+        };
+
+        // like the plain `format!("{:.2}", 5)` that was used before
+        assert_eq!(format_number(&5, true, &options), format!("{:.2}", 5));
+        assert_eq!(format_number(&5, true, &options), "5");
+        assert_eq!(format_number(&-1234_i64, true, &options), "-1234");
+        assert_eq!(format(5.0, options), "5.00");
+
+        // currencies still show their minor units and compact numbers their fraction
+        let euros = NumberRenderOptions {
+            currency: Some("EUR"),
+            ..Default::default()
+        };
+        assert_eq!(format_number(&5, true, &euros), "€5.00");
+        let compact = NumberRenderOptions {
+            notation: Some(Notation::Compact),
+            precision: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(format_number(&1234, true, &compact), "1.2k");
+    }
+
+    #[test]
+    fn currency_and_unit() {
+        let eur = NumberRenderOptions {
+            currency: Some("EUR"),
+            thousands_separator: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(format(-1234.5, eur), "-€1,234.50");
+        assert_eq!(
+            format(
+                1234.5,
+                NumberRenderOptions {
+                    locale: Some(NumberLocale::DE),
+                    ..eur
+                }
+            ),
+            "1.234,50\u{a0}€"
+        );
+        assert_eq!(
+            format(
+                3.0,
+                NumberRenderOptions {
+                    currency: Some("CHF"),
+                    ..Default::default()
+                }
+            ),
+            "CHF\u{a0}3.00"
+        );
+        assert_eq!(
+            format_number(
+                &1500,
+                true,
+                &NumberRenderOptions {
+                    currency: Some("JPY"),
+                    ..Default::default()
+                }
+            ),
+            "¥1500"
+        );
+        assert_eq!(
+            format(
+                12.5,
+                NumberRenderOptions {
+                    unit: Some("kg"),
+                    ..Default::default()
+                }
+            ),
+            "12.5\u{a0}kg"
+        );
+    }
+
+    #[test]
+    fn compact_notation() {
+        let compact = NumberRenderOptions {
+            notation: Some(Notation::Compact),
+            ..Default::default()
+        };
+
+        assert_eq!(format(999.0, compact), "999");
+        assert_eq!(format(1234.0, compact), "1.2k");
+        assert_eq!(format(12345.0, compact), "12k");
+        assert_eq!(format(123456.0, compact), "123k");
+        assert_eq!(format(999_960.0, compact), "1M");
+        assert_eq!(format(-2_500_000.0, compact), "-2.5M");
+        assert_eq!(
+            format_number(&u128::MAX, true, &compact),
+            "340282366920938463463374607T"
+        );
+        assert_eq!(
+            format(
+                1234.0,
+                NumberRenderOptions {
+                    precision: Some(2),
+                    ..compact
+                }
+            ),
+            "1.23k"
+        );
+    }
+}
@@ -1,24 +1,145 @@
 use leptos::prelude::*;
+use std::ops::Range;
 
 /// You can pass this to a [`TableContent`] component's `reload_controller` prop to trigger a reload.
 ///
 /// See the [paginated_rest_datasource example](https://github.com/Synphonyte/leptos-struct-table/blob/master/examples/paginated_rest_datasource/src/main.rs)
 /// for how to use.
 #[derive(Copy, Clone)]
-pub struct ReloadController(Trigger);
+pub struct ReloadController {
+    reload: Trigger,
+    soft_reload: Trigger,
+    /// The ranges given to [`Self::invalidate_rows`] that have not been handled yet, one queue
+    /// per table that uses this controller so every table receives all of them.
+    invalidated: StoredValue<Vec<ArcRwSignal<Vec<Range<usize>>>>>,
+}
 
 impl Default for ReloadController {
     fn default() -> Self {
-        Self(Trigger::new())
+        Self {
+            reload: Trigger::new(),
+            soft_reload: Trigger::new(),
+            invalidated: StoredValue::new(vec![]),
+        }
     }
 }
 
 impl ReloadController {
+    /// Discards all loaded rows and loads them again. Until then the rows are shown as loading.
     pub fn reload(&self) {
-        self.0.notify();
+        self.reload.notify();
+    }
+
+    /// Reloads the visible rows in the background while the currently loaded rows stay visible.
+    /// The loaded rows are then updated in place so the cells are not rendered again from scratch.
+    /// Rows that are not visible are discarded and loaded again once they become visible.
+    /// This is useful for data that is refreshed periodically.
+    ///
+    /// The selection and edited rows are kept.
+    pub fn soft_reload(&self) {
+        self.soft_reload.notify();
+    }
+
+    /// Discards only the rows in `range` and loads them again if they are visible. Use this
+    /// if you know which rows have changed to avoid reloading the whole table.
+    pub fn invalidate_rows(&self, range: Range<usize>) {
+        self.invalidated.with_value(|tables| {
+            for invalidated in tables {
+                invalidated.update(|invalidated| invalidated.push(range.clone()));
+            }
+        });
+    }
+
+    /// Discards the row at `index` and loads it again if it is visible.
+    /// See [`Self::invalidate_rows`].
+    pub fn invalidate_row(&self, index: usize) {
+        self.invalidate_rows(index..index + 1);
+    }
+
+    /// Registers a table that receives the ranges given to [`Self::invalidate_rows`] from now on.
+    /// It is unregistered when the current reactive owner is cleaned up.
+    pub(crate) fn subscribe_invalidated(&self) -> InvalidatedRows {
+        let invalidated = ArcRwSignal::new(vec![]);
+
+        self.invalidated
+            .update_value(|tables| tables.push(invalidated.clone()));
+
+        on_cleanup({
+            let tables = self.invalidated;
+            let invalidated = invalidated.clone();
+
+            move || {
+                tables.try_update_value(|tables| tables.retain(|other| other != &invalidated));
+            }
+        });
+
+        InvalidatedRows(invalidated)
     }
 
     pub fn track(&self) {
+        self.reload.track();
+    }
+
+    pub fn track_soft_reload(&self) {
+        self.soft_reload.track();
+    }
+}
+
+/// The ranges invalidated for one table. Returned by [`ReloadController::subscribe_invalidated`].
+pub(crate) struct InvalidatedRows(ArcRwSignal<Vec<Range<usize>>>);
+
+impl InvalidatedRows {
+    /// Tracks and takes the ranges given to [`ReloadController::invalidate_rows`] since the last call.
+    pub fn take(&self) -> Vec<Range<usize>> {
         self.0.track();
+        self.0
+            .try_update_untracked(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_table_receives_all_invalidated_ranges() {
+        let owner = Owner::new();
+        owner.set();
+
+        let controller = ReloadController::default();
+        let first = controller.subscribe_invalidated();
+        let second = controller.subscribe_invalidated();
+
+        controller.invalidate_rows(10..20);
+        controller.invalidate_row(5);
+
+        assert_eq!(first.take(), vec![10..20, 5..6]);
+        assert!(first.take().is_empty());
+
+        controller.invalidate_row(7);
+
+        assert_eq!(second.take(), vec![10..20, 5..6, 7..8]);
+        assert_eq!(first.take(), vec![7..8]);
+    }
+
+    #[test]
+    fn tables_are_unsubscribed_on_cleanup() {
+        let owner = Owner::new();
+        owner.set();
+
+        let controller = ReloadController::default();
+        let _kept = controller.subscribe_invalidated();
+
+        let table_owner = owner.child();
+        table_owner.with(|| controller.subscribe_invalidated());
+        assert_eq!(controller.invalidated.with_value(Vec::len), 2);
+
+        table_owner.cleanup();
+        assert_eq!(controller.invalidated.with_value(Vec::len), 1);
+
+        // ranges invalidated before a table subscribes are not received
+        controller.invalidate_row(1);
+        assert!(controller.subscribe_invalidated().take().is_empty());
     }
 }
@@ -1,27 +1,202 @@
-use crate::loaded_rows::RowState;
-use std::cell::RefCell;
+use crate::AbortToken;
+use crate::TableDataProvider;
+use crate::data_provider_cell::DataProviderCell;
+use crate::loaded_rows::{CacheMetrics, RowState};
+use leptos::prelude::{Owner, RwSignal, Signal};
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
+use std::future::Future;
+use std::ops::Range;
+use std::pin::Pin;
 use std::rc::Rc;
 
 /// Allows you to read the cached state of rows from inside the table component which handles
 /// loading and caching automatically.
-#[derive(Clone)]
-pub struct RowReader<Row: Send + Sync + 'static> {
+///
+/// It can also request rows from the data provider of the table, for example to export them
+/// with [`CsvExporter`](crate::CsvExporter). These are sorted and filtered like the table.
+pub struct RowReader<Row: Send + Sync + 'static, Column: Send + Sync + 'static = usize> {
     pub(crate) get_loaded_rows: LoadedRowsGetter<Row>,
+    pub(crate) get_cache_metrics: CacheMetricsGetter,
+    pub(crate) get_rows: RowsGetter<Row>,
+    pub(crate) get_row_count: RowCountGetter,
+    pub(crate) chunk_size: Rc<Cell<Option<usize>>>,
+    pub(crate) columns: Rc<Cell<Option<Signal<Vec<Column>>>>>,
+    pub(crate) owner: Rc<RefCell<Option<Owner>>>,
+    pub(crate) running_exports: Rc<RefCell<Vec<AbortToken>>>,
+}
+
+impl<Row: Send + Sync + 'static, Column: Send + Sync + 'static> Clone for RowReader<Row, Column> {
+    fn clone(&self) -> Self {
+        Self {
+            get_loaded_rows: Rc::clone(&self.get_loaded_rows),
+            get_cache_metrics: Rc::clone(&self.get_cache_metrics),
+            get_rows: Rc::clone(&self.get_rows),
+            get_row_count: Rc::clone(&self.get_row_count),
+            chunk_size: Rc::clone(&self.chunk_size),
+            columns: Rc::clone(&self.columns),
+            owner: Rc::clone(&self.owner),
+            running_exports: Rc::clone(&self.running_exports),
+        }
+    }
 }
 
 pub type LoadedRowsGetter<Row> = Rc<RefCell<Box<dyn Fn(usize) -> RowState<Row>>>>;
 
-impl<Row: Send + Sync + 'static> Default for RowReader<Row> {
+pub type CacheMetricsGetter = Rc<RefCell<Box<dyn Fn() -> CacheMetrics>>>;
+
+pub type RowsGetter<Row> = Rc<
+    RefCell<
+        Box<
+            dyn Fn(
+                Range<usize>,
+                AbortToken,
+            )
+                -> Pin<Box<dyn Future<Output = Result<(Vec<Row>, Range<usize>), String>>>>,
+        >,
+    >,
+>;
+
+pub type RowCountGetter =
+    Rc<RefCell<Box<dyn Fn() -> Pin<Box<dyn Future<Output = Option<usize>>>>>>>;
+
+impl<Row: Send + Sync + 'static, Column: Send + Sync + 'static> Default for RowReader<Row, Column> {
     fn default() -> Self {
         Self {
             get_loaded_rows: Rc::new(RefCell::new(Box::new(|_| RowState::Placeholder))),
+            get_cache_metrics: Rc::new(RefCell::new(Box::new(CacheMetrics::default))),
+            get_rows: Rc::new(RefCell::new(Box::new(|_, _| {
+                Box::pin(async { Err("the table hasn't been rendered yet".to_string()) })
+            }))),
+            get_row_count: Rc::new(RefCell::new(Box::new(|| Box::pin(async { None })))),
+            chunk_size: Rc::new(Cell::new(None)),
+            columns: Rc::new(Cell::new(None)),
+            owner: Rc::new(RefCell::new(None)),
+            running_exports: Rc::new(RefCell::new(vec![])),
         }
     }
 }
 
-impl<Row: Send + Sync + 'static> RowReader<Row> {
+impl<Row: Send + Sync + 'static, Column: Send + Sync + 'static> RowReader<Row, Column> {
     /// Returns the cached state of the row at the given index
     pub fn cached_row(&self, index: usize) -> RowState<Row> {
         (*self.get_loaded_rows.borrow())(index)
     }
+
+    /// Returns statistics about the row cache like the number of loaded rows and evictions.
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        (*self.get_cache_metrics.borrow())()
+    }
+
+    /// Requests the rows in `range` from the data provider of the table with its current sorting and
+    /// filter. The rows aren't cached. Errors of the data provider are formatted with `Debug`.
+    pub async fn get_rows(
+        &self,
+        range: Range<usize>,
+        abort_token: AbortToken,
+    ) -> Result<(Vec<Row>, Range<usize>), String> {
+        let rows = (*self.get_rows.borrow())(range, abort_token);
+        rows.await
+    }
+
+    /// The total number of rows as returned by [`TableDataProvider::row_count`] of the table.
+    pub async fn row_count(&self) -> Option<usize> {
+        let row_count = (*self.get_row_count.borrow())();
+        row_count.await
+    }
+
+    /// The [`TableDataProvider::CHUNK_SIZE`] of the data provider of the table.
+    pub fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size.get()
+    }
+
+    /// The columns that the table shows in their order. `None` if the table hasn't been rendered yet.
+    /// The exporters use them unless they are given other columns.
+    pub fn columns(&self) -> Option<Signal<Vec<Column>>> {
+        self.columns.get()
+    }
+
+    /// Requests the rows from `rows`. Called by the table with its data provider and its columns.
+    pub(crate) fn connect<DataP, Err>(
+        &self,
+        rows: &Rc<DataProviderCell<DataP>>,
+        columns: RwSignal<Vec<Column>>,
+    ) where
+        DataP: TableDataProvider<Row, Column, Err> + 'static,
+        Err: Debug,
+    {
+        let _ = self.get_rows.replace(Box::new({
+            let rows = Rc::clone(rows);
+
+            move |range, abort_token| {
+                let rows = Rc::clone(&rows);
+
+                Box::pin(async move {
+                    rows.provider()
+                        .await
+                        .get_rows_with_context(range, abort_token)
+                        .await
+                        .map_err(|err| format!("{err:?}"))
+                })
+            }
+        }));
+
+        let _ = self.get_row_count.replace(Box::new({
+            let rows = Rc::clone(rows);
+
+            move || {
+                let rows = Rc::clone(&rows);
+                Box::pin(async move { rows.provider().await.row_count().await })
+            }
+        }));
+
+        self.chunk_size.set(DataP::CHUNK_SIZE);
+        self.columns.set(Some(columns.into()));
+        let _ = self.owner.replace(Owner::current());
+    }
+
+    /// Aborts `abort_token` when the sorting or the filter of the table changes until the returned
+    /// guard is dropped. Used by the exports so they don't mix rows of different orders.
+    pub(crate) fn abort_on_change(&self, abort_token: &AbortToken) -> RunningExport {
+        self.running_exports.borrow_mut().push(abort_token.clone());
+
+        RunningExport {
+            running_exports: Rc::clone(&self.running_exports),
+            abort_token: abort_token.clone(),
+        }
+    }
+
+    /// Called by the table when its sorting or filter changes.
+    pub(crate) fn sorting_or_filter_changed(&self) {
+        let running_exports = self.running_exports.borrow().clone();
+
+        for abort_token in running_exports {
+            abort_token.abort();
+        }
+    }
+
+    /// Runs `f` in the reactive owner of the table, so the cells that are built by `f` for an export
+    /// see the contexts of the table like the rendered ones.
+    pub(crate) fn with_table_owner<T>(&self, f: impl FnOnce() -> T) -> T {
+        let owner = self.owner.borrow().clone();
+
+        match owner {
+            Some(owner) => owner.with(f),
+            None => f(),
+        }
+    }
+}
+
+/// Returned by [`RowReader::abort_on_change`].
+pub(crate) struct RunningExport {
+    running_exports: Rc<RefCell<Vec<AbortToken>>>,
+    abort_token: AbortToken,
+}
+
+impl Drop for RunningExport {
+    fn drop(&mut self) {
+        self.running_exports
+            .borrow_mut()
+            .retain(|abort_token| !abort_token.ptr_eq(&self.abort_token));
+    }
 }
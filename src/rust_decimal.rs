@@ -1,12 +1,51 @@
 //! Support for [::rust_decimal] crate.
+use crate::number_format::format_number;
 use crate::*;
 use ::rust_decimal::Decimal;
 use leptos::prelude::*;
 
-#[derive(Clone, Default)]
+/// The same options as [`NumberRenderOptions`].
+#[derive(Clone, Copy, Default)]
 pub struct DecimalNumberRenderOptions {
     /// Specifies the number of digits to display after the decimal point
     pub precision: Option<usize>,
+    /// Rounds to this number of significant digits instead. Takes precedence over `precision`
+    /// unless the notation is compact.
+    pub significant_digits: Option<usize>,
+    /// Groups the integer digits by thousands. Defaults to [`NumberLocale::use_grouping`].
+    pub thousands_separator: Option<bool>,
+    /// Overrides [`NumberLocale::decimal_separator`].
+    pub decimal_separator: Option<char>,
+    /// When to show the sign. Defaults to [`SignDisplay::Auto`].
+    pub sign_display: Option<SignDisplay>,
+    /// Multiplies the value by 100 and appends the percent sign.
+    pub percent: Option<bool>,
+    /// Use [`Notation::Compact`] to abbreviate large numbers like "1.2k".
+    pub notation: Option<Notation>,
+    /// Overrides the locale of the table, for example `locale = "de"`.
+    pub locale: Option<NumberLocale>,
+    /// Shows the value as an amount of this currency. An ISO 4217 code like `"EUR"` is replaced by its
+    /// symbol which is placed according to the locale. Defaults the precision to the minor units of the currency.
+    pub currency: Option<&'static str>,
+    /// Appended to the value, for example `"kg"`.
+    pub unit: Option<&'static str>,
+}
+
+impl From<DecimalNumberRenderOptions> for NumberRenderOptions {
+    fn from(options: DecimalNumberRenderOptions) -> Self {
+        Self {
+            precision: options.precision,
+            significant_digits: options.significant_digits,
+            thousands_separator: options.thousands_separator,
+            decimal_separator: options.decimal_separator,
+            sign_display: options.sign_display,
+            percent: options.percent,
+            notation: options.notation,
+            locale: options.locale,
+            currency: options.currency,
+            unit: options.unit,
+        }
+    }
 }
 /// Implementation for [`Decimal`] to work with the [`TableRow`] derive and the [`DefaultTableCellRenderer`]
 /// ```
@@ -23,10 +62,29 @@ pub struct DecimalNumberRenderOptions {
 impl CellValue<Decimal> for Decimal {
     type RenderOptions = DecimalNumberRenderOptions;
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.precision.as_ref() {
-            format!("{:.value$}", self)
-        } else {
-            self.to_string()
-        }
+        self.format_text(&options)
+    }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+
+    #[cfg(feature = "xlsx")]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
+    }
+
+    fn alignment(_options: &Self::RenderOptions) -> CellAlignment {
+        CellAlignment::End
+    }
+
+    fn set_default_locale(options: &mut Self::RenderOptions, locale: NumberLocale) {
+        options.locale.get_or_insert(locale);
+    }
+}
+
+impl TextCellValue<Decimal> for Decimal {
+    fn format_text(&self, options: &Self::RenderOptions) -> String {
+        format_number(self, false, &(*options).into())
     }
 }
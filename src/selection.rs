@@ -1,9 +1,13 @@
+use crate::FilterModel;
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::Arc;
 
 /// Type of selection together with the `RwSignal` to hold the selection
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
-pub enum Selection {
+#[derive(Default)]
+pub enum Selection<Row, Column = usize> {
     /// No selection possible (the default).
     #[default]
     None,
@@ -12,12 +16,70 @@ pub enum Selection {
     /// `Some(<row index>)` if a row is selected.
     Single(RwSignal<Option<usize>>),
 
-    /// Allow multiple rows to be selected at a time. Each entry in the `Vec`
-    /// is the index of a selected row.
-    Multiple(RwSignal<HashSet<usize>>),
+    /// Allow multiple rows to be selected at a time. The rows are identified by their index.
+    /// See [`RowSelection`] for how to select all rows.
+    Multiple(RwSignal<RowSelection<usize, Column>>),
+
+    /// Allow multiple rows to be selected at a time. The rows are identified by their key
+    /// as returned by `row_key`. It's also used for the `row_key` prop of [`TableContent`]
+    /// if that isn't given.
+    ///
+    /// Unlike the other modes this selection is kept when the table is sorted, filtered or
+    /// reloaded. It can also contain rows that are not loaded at the moment.
+    MultipleByKey {
+        keys: RwSignal<RowSelection<String, Column>>,
+        row_key: RowKeyGetter<Row>,
+    },
+}
+
+impl<Row, Column> Clone for Selection<Row, Column> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
-impl Selection {
+impl<Row, Column> Copy for Selection<Row, Column> {}
+
+impl<Row, Column> PartialEq for Selection<Row, Column> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Selection::None, Selection::None) => true,
+            (Selection::Single(a), Selection::Single(b)) => a == b,
+            (Selection::Multiple(a), Selection::Multiple(b)) => a == b,
+            (
+                Selection::MultipleByKey { keys, row_key },
+                Selection::MultipleByKey {
+                    keys: other_keys,
+                    row_key: other_row_key,
+                },
+            ) => keys == other_keys && row_key == other_row_key,
+            _ => false,
+        }
+    }
+}
+
+impl<Row, Column> Eq for Selection<Row, Column> {}
+
+impl<Row, Column> std::fmt::Debug for Selection<Row, Column> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Selection::None => write!(f, "None"),
+            Selection::Single(selected_index) => {
+                f.debug_tuple("Single").field(selected_index).finish()
+            }
+            Selection::Multiple(selected_indices) => {
+                f.debug_tuple("Multiple").field(selected_indices).finish()
+            }
+            Selection::MultipleByKey { keys, row_key } => f
+                .debug_struct("MultipleByKey")
+                .field("keys", keys)
+                .field("row_key", row_key)
+                .finish(),
+        }
+    }
+}
+
+impl<Row, Column: Send + Sync + 'static> Selection<Row, Column> {
     /// Clear the selection
     pub fn clear(&self) {
         match self {
@@ -26,8 +88,454 @@ impl Selection {
                 selected_index.set(None);
             }
             Selection::Multiple(selected_indices) => {
-                selected_indices.write().clear();
+                selected_indices.write().deselect_all();
+            }
+            Selection::MultipleByKey {
+                keys: selected_keys,
+                ..
+            } => {
+                selected_keys.write().deselect_all();
+            }
+        }
+    }
+
+    /// Selects all rows including the ones that are not loaded. Does nothing if only a single
+    /// row can be selected. See [`RowSelection::select_all`].
+    pub fn select_all(&self) {
+        match self {
+            Selection::None | Selection::Single(_) => {}
+            Selection::Multiple(selected_indices) => {
+                selected_indices.write().select_all();
+            }
+            Selection::MultipleByKey {
+                keys: selected_keys,
+                ..
+            } => {
+                selected_keys.write().select_all();
+            }
+        }
+    }
+
+    /// Selects all rows that match `filter` including the ones that are not loaded. Does nothing
+    /// if only a single row can be selected. See [`RowSelection::select_all_matching`].
+    pub fn select_all_matching(&self, filter: FilterModel<Column>) {
+        match self {
+            Selection::None | Selection::Single(_) => {}
+            Selection::Multiple(selected_indices) => {
+                selected_indices.write().select_all_matching(filter);
+            }
+            Selection::MultipleByKey {
+                keys: selected_keys,
+                ..
+            } => {
+                selected_keys.write().select_all_matching(filter);
+            }
+        }
+    }
+
+    /// Same as [`Self::clear`]
+    pub fn deselect_all(&self) {
+        self.clear();
+    }
+
+    /// Selects all rows that are not selected and deselects all that are. Does nothing if only
+    /// a single row can be selected. See [`RowSelection::invert`].
+    pub fn invert(&self) {
+        match self {
+            Selection::None | Selection::Single(_) => {}
+            Selection::Multiple(selected_indices) => {
+                selected_indices.write().invert();
+            }
+            Selection::MultipleByKey {
+                keys: selected_keys,
+                ..
+            } => {
+                selected_keys.write().invert();
+            }
+        }
+    }
+
+    /// Returns `true` if the selection is kept when the table is sorted, filtered or reloaded.
+    pub fn is_persistent(&self) -> bool {
+        matches!(self, Selection::MultipleByKey { .. })
+    }
+
+    /// The key getter of a [`Selection::MultipleByKey`].
+    pub(crate) fn row_key(&self) -> Option<RowKeyGetter<Row>> {
+        match self {
+            Selection::MultipleByKey { row_key, .. } => Some(*row_key),
+            _ => None,
+        }
+    }
+}
+
+/// The rows selected by [`Selection::Multiple`] or [`Selection::MultipleByKey`] identified by
+/// their index or key `K`.
+///
+/// Selecting all rows doesn't need them to be loaded. Instead it is represented symbolically as
+/// [`RowSelection::AllExcept`] or, for all rows that match a filter, as [`RowSelection::AllMatching`]
+/// which can be sent to the server to act on the selected rows there.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// let mut selection = RowSelection::<usize>::default();
+/// selection.select_all();
+/// selection.remove(&3);
+///
+/// assert!(selection.contains(&1));
+/// assert!(!selection.contains(&3));
+/// assert_eq!(selection.selected_count(10), 9);
+///
+/// selection.invert();
+/// assert_eq!(selection, RowSelection::Only([3].into()));
+///
+/// let mut filter = FilterModel::default();
+/// filter.search = "tolkien".to_string();
+///
+/// selection.select_all_matching(filter.clone());
+/// assert_eq!(selection.filter(), Some(&filter));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize + Eq + Hash, Column: Serialize",
+    deserialize = "K: Deserialize<'de> + Eq + Hash, Column: Deserialize<'de>"
+))]
+pub enum RowSelection<K: Eq + Hash, Column = usize> {
+    /// Only these rows are selected.
+    Only(HashSet<K>),
+    /// All rows except these are selected.
+    AllExcept(HashSet<K>),
+    /// All rows that match `filter` except the rows in `except` are selected.
+    AllMatching {
+        filter: FilterModel<Column>,
+        except: HashSet<K>,
+    },
+}
+
+impl<K: Eq + Hash, Column> Default for RowSelection<K, Column> {
+    fn default() -> Self {
+        Self::Only(HashSet::new())
+    }
+}
+
+impl<K: Eq + Hash, Column> From<HashSet<K>> for RowSelection<K, Column> {
+    fn from(keys: HashSet<K>) -> Self {
+        Self::Only(keys)
+    }
+}
+
+impl<K: Eq + Hash, Column> Extend<K> for RowSelection<K, Column> {
+    /// Selects all given rows.
+    fn extend<I: IntoIterator<Item = K>>(&mut self, keys: I) {
+        for key in keys {
+            self.insert(key);
+        }
+    }
+}
+
+impl<K: Eq + Hash, Column> RowSelection<K, Column> {
+    /// Returns `true` if the row is selected. For [`RowSelection::AllMatching`] the row is
+    /// assumed to match the filter.
+    pub fn contains(&self, key: &K) -> bool {
+        match self {
+            RowSelection::Only(keys) => keys.contains(key),
+            RowSelection::AllExcept(keys) | RowSelection::AllMatching { except: keys, .. } => {
+                !keys.contains(key)
+            }
+        }
+    }
+
+    /// Selects the row.
+    pub fn insert(&mut self, key: K) {
+        match self {
+            RowSelection::Only(keys) => {
+                keys.insert(key);
+            }
+            RowSelection::AllExcept(keys) | RowSelection::AllMatching { except: keys, .. } => {
+                keys.remove(&key);
+            }
+        }
+    }
+
+    /// Deselects the row.
+    pub fn remove(&mut self, key: &K)
+    where
+        K: Clone,
+    {
+        match self {
+            RowSelection::Only(keys) => {
+                keys.remove(key);
+            }
+            RowSelection::AllExcept(keys) | RowSelection::AllMatching { except: keys, .. } => {
+                keys.insert(key.clone());
+            }
+        }
+    }
+
+    /// Selects the row if it isn't selected and deselects it otherwise.
+    pub fn toggle(&mut self, key: K) {
+        let keys = match self {
+            RowSelection::Only(keys)
+            | RowSelection::AllExcept(keys)
+            | RowSelection::AllMatching { except: keys, .. } => keys,
+        };
+        if !keys.remove(&key) {
+            keys.insert(key);
+        }
+    }
+
+    /// Returns `true` if all rows are selected, i.e. this is an [`RowSelection::AllExcept`]
+    /// without exceptions.
+    pub fn is_all(&self) -> bool {
+        matches!(self, RowSelection::AllExcept(keys) if keys.is_empty())
+    }
+
+    /// The filter the selected rows have to match if this is a [`RowSelection::AllMatching`].
+    pub fn filter(&self) -> Option<&FilterModel<Column>> {
+        match self {
+            RowSelection::AllMatching { filter, .. } => Some(filter),
+            _ => None,
+        }
+    }
+
+    /// Selects all rows.
+    pub fn select_all(&mut self) {
+        *self = RowSelection::AllExcept(HashSet::new());
+    }
+
+    /// Selects all rows that match `filter` without enumerating them. The previous selection
+    /// is replaced. Send the selection to the server to act on the matching rows there.
+    pub fn select_all_matching(&mut self, filter: FilterModel<Column>) {
+        *self = RowSelection::AllMatching {
+            filter,
+            except: HashSet::new(),
+        };
+    }
+
+    /// Deselects all rows.
+    pub fn deselect_all(&mut self) {
+        *self = RowSelection::Only(HashSet::new());
+    }
+
+    /// Selects all rows that are not selected and deselects all that are. For
+    /// [`RowSelection::AllMatching`] only the matching rows are inverted, i.e. only the
+    /// rows that were excepted are selected afterwards.
+    pub fn invert(&mut self) {
+        *self = match std::mem::take(self) {
+            RowSelection::Only(keys) => RowSelection::AllExcept(keys),
+            RowSelection::AllExcept(keys) | RowSelection::AllMatching { except: keys, .. } => {
+                RowSelection::Only(keys)
             }
+        };
+    }
+
+    /// Returns `true` if no row is selected. An [`RowSelection::AllExcept`] or
+    /// [`RowSelection::AllMatching`] selection is never considered empty because the total
+    /// number of rows isn't known here.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, RowSelection::Only(keys) if keys.is_empty())
+    }
+
+    /// The number of selected rows given the total number of rows in the table. For
+    /// [`RowSelection::AllMatching`] `row_count` has to be the number of matching rows.
+    pub fn selected_count(&self, row_count: usize) -> usize {
+        match self {
+            RowSelection::Only(keys) => keys.len(),
+            RowSelection::AllExcept(keys) | RowSelection::AllMatching { except: keys, .. } => {
+                row_count.saturating_sub(keys.len())
+            }
+        }
+    }
+
+    /// The selected rows out of the rows that a table shows with the given `filter`.
+    /// Rows selected by a [`RowSelection::AllMatching`] with a different filter are not known
+    /// to be shown so none is considered selected.
+    pub(crate) fn shown_with_filter(&self, shown_filter: &FilterModel<Column>) -> RowSelection<K>
+    where
+        K: Clone,
+        Column: PartialEq,
+    {
+        match self {
+            RowSelection::Only(keys) => RowSelection::Only(keys.clone()),
+            RowSelection::AllExcept(keys) => RowSelection::AllExcept(keys.clone()),
+            RowSelection::AllMatching { filter, except } if filter == shown_filter => {
+                RowSelection::AllExcept(except.clone())
+            }
+            RowSelection::AllMatching { .. } => RowSelection::default(),
         }
     }
 }
+
+/// Returns the key of a row. Can be passed to the `row_key` prop of [`TableContent`] to
+/// identify the rows independently of their index, and is needed for a [`Selection::MultipleByKey`].
+///
+/// Like a [`Callback`] it is `Copy` and lives as long as the reactive owner it was created in.
+pub struct RowKeyGetter<Row>(StoredValue<Arc<RowKeyFn<Row>>>);
+
+type RowKeyFn<Row> = dyn Fn(&Row) -> Option<String> + Send + Sync;
+
+impl<F, Row> From<F> for RowKeyGetter<Row>
+where
+    F: Fn(&Row) -> String + Send + Sync + 'static,
+    Row: 'static,
+{
+    fn from(f: F) -> Self {
+        Self(StoredValue::new(Arc::new(move |row| Some(f(row)))))
+    }
+}
+
+impl<Row> Clone for RowKeyGetter<Row> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Row> Copy for RowKeyGetter<Row> {}
+
+impl<Row> PartialEq for RowKeyGetter<Row> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Row> Eq for RowKeyGetter<Row> {}
+
+impl<Row> std::fmt::Debug for RowKeyGetter<Row> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RowKeyGetter").finish()
+    }
+}
+
+impl<Row: 'static> RowKeyGetter<Row> {
+    /// Returns `None` if the reactive owner of the getter has been disposed.
+    #[inline]
+    pub fn run(&self, row: &Row) -> Option<String> {
+        self.0.try_with_value(|f| f(row)).flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColumnFilter;
+
+    fn tolkien() -> FilterModel<usize> {
+        let mut filter = FilterModel::default();
+        filter.set(1, ColumnFilter::Contains("tolkien".to_string()));
+        filter
+    }
+
+    #[test]
+    fn only_and_all_except_are_inverse() {
+        let mut selection = RowSelection::<usize>::default();
+        selection.insert(1);
+        selection.toggle(2);
+        selection.toggle(1);
+
+        assert_eq!(selection, RowSelection::Only([2].into()));
+        assert_eq!(selection.selected_count(10), 1);
+
+        selection.invert();
+        assert_eq!(selection, RowSelection::AllExcept([2].into()));
+        assert!(selection.contains(&1));
+        assert!(!selection.contains(&2));
+        assert_eq!(selection.selected_count(10), 9);
+        assert!(!selection.is_empty());
+
+        selection.insert(2);
+        assert!(selection.is_all());
+
+        selection.deselect_all();
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn all_matching_keeps_the_filter() {
+        let mut selection = RowSelection::<usize>::default();
+        selection.select_all_matching(tolkien());
+        selection.remove(&4);
+
+        assert_eq!(selection.filter(), Some(&tolkien()));
+        assert!(selection.contains(&3));
+        assert!(!selection.contains(&4));
+        assert!(!selection.is_all());
+        assert!(!selection.is_empty());
+        assert_eq!(selection.selected_count(5), 4);
+
+        selection.toggle(4);
+        assert!(selection.contains(&4));
+
+        selection.remove(&4);
+        selection.invert();
+        assert_eq!(selection, RowSelection::Only([4].into()));
+        assert_eq!(selection.filter(), None);
+    }
+
+    #[test]
+    fn all_matching_is_only_shown_with_its_filter() {
+        let mut selection = RowSelection::<usize>::default();
+        selection.select_all_matching(tolkien());
+        selection.remove(&4);
+
+        assert_eq!(
+            selection.shown_with_filter(&tolkien()),
+            RowSelection::AllExcept([4].into())
+        );
+        assert_eq!(
+            selection.shown_with_filter(&FilterModel::default()),
+            RowSelection::default()
+        );
+
+        selection.select_all();
+        assert_eq!(
+            selection.shown_with_filter(&tolkien()),
+            RowSelection::AllExcept(HashSet::new())
+        );
+    }
+
+    #[test]
+    fn extend_selects_the_given_rows() {
+        let mut selection = RowSelection::<usize>::AllExcept([1, 2, 5].into());
+        selection.extend(1..=3);
+
+        assert_eq!(selection, RowSelection::AllExcept([5].into()));
+    }
+
+    #[test]
+    fn selection_multiple_without_loading_rows() {
+        let owner = Owner::new();
+        owner.set();
+
+        let selected = RwSignal::new(RowSelection::Only([1].into()));
+        let selection = Selection::<()>::Multiple(selected);
+
+        selection.select_all();
+        assert!(selected.read().is_all());
+
+        selection.invert();
+        assert!(selected.read().is_empty());
+
+        selection.select_all_matching(tolkien());
+        assert_eq!(selected.read().filter(), Some(&tolkien()));
+
+        selection.clear();
+        assert_eq!(*selected.read(), RowSelection::default());
+        assert!(!selection.is_persistent());
+    }
+
+    #[test]
+    fn single_selection_ignores_select_all() {
+        let owner = Owner::new();
+        owner.set();
+
+        let selected = RwSignal::new(Some(3));
+        let selection = Selection::<usize>::Single(selected);
+
+        selection.select_all();
+        selection.invert();
+        assert_eq!(selected.get_untracked(), Some(3));
+
+        selection.deselect_all();
+        assert_eq!(selected.get_untracked(), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{ColumnSort, TableHeadEvent};
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 
 /// Sorting mode
@@ -49,3 +50,428 @@ impl SortingMode {
         }
     }
 }
+
+/// Where rows with a `None` value end up when sorting by a column of type `Option<T>`.
+/// Used by [`compare_optional_values`].
+///
+/// The `Vec` data provider generated by `#[table(sortable, impl_vec_data_provider)]` compares the
+/// values of the columns with their `PartialOrd` implementation which is the same as `Smallest`:
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_struct_table::*;
+/// # use std::collections::VecDeque;
+/// #
+/// #[derive(TableRow, Clone)]
+/// #[table(sortable, impl_vec_data_provider, column_index_type = "enum")]
+/// pub struct Book {
+///     title: String,
+///     year: Option<u32>,
+/// }
+///
+/// let mut books = vec![
+///     Book { title: "The Lord of the Rings".to_string(), year: Some(1954) },
+///     Book { title: "Unknown".to_string(), year: None },
+///     Book { title: "The Hobbit".to_string(), year: Some(1937) },
+/// ];
+///
+/// books.set_sorting(&VecDeque::from([(BookColumn::Year, ColumnSort::Ascending)]));
+/// assert_eq!(books[0].title, "Unknown");
+/// assert_eq!(books[1].title, "The Hobbit");
+///
+/// books.set_sorting(&VecDeque::from([(BookColumn::Year, ColumnSort::Descending)]));
+/// assert_eq!(books[0].title, "The Lord of the Rings");
+/// assert_eq!(books[2].title, "Unknown");
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum NoneSortOrder {
+    /// `None` is smaller than any `Some(...)` just like the `Ord` implementation of `Option`.
+    /// So it comes first when sorting ascending and last when sorting descending.
+    #[default]
+    Smallest,
+
+    /// `None` always comes first regardless of the sort direction
+    First,
+
+    /// `None` always comes last regardless of the sort direction
+    Last,
+}
+
+/// Implement this for your row type to define how it is sorted on the client by each column.
+/// Used by [`sort_rows_by_column`] and [`VecDataProvider`].
+///
+/// This is where a column can be sorted by something else than its value, like a
+/// case insensitive key, or where `None` values are placed with [`compare_optional_values`].
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use std::cmp::Ordering;
+/// # use std::collections::VecDeque;
+/// #
+/// #[derive(Clone, Copy)]
+/// enum BookColumn {
+///     Title,
+///     Year,
+/// }
+///
+/// struct Book {
+///     title: String,
+///     year: Option<u16>,
+/// }
+///
+/// impl SortableRow<BookColumn> for Book {
+///     fn compare_by(&self, other: &Self, column: BookColumn, sort: ColumnSort) -> Ordering {
+///         match column {
+///             // sort by a key instead of the value
+///             BookColumn::Title => compare_values(&self.title.to_lowercase(), &other.title.to_lowercase()),
+///             // books without a year always come last
+///             BookColumn::Year => {
+///                 compare_optional_values(self.year.as_ref(), other.year.as_ref(), sort, NoneSortOrder::Last)
+///             }
+///         }
+///     }
+/// }
+///
+/// let mut books = vec![
+///     Book { title: "b".to_string(), year: None },
+///     Book { title: "C".to_string(), year: Some(1954) },
+///     Book { title: "a".to_string(), year: Some(1937) },
+/// ];
+///
+/// sort_rows_by_column(&mut books, &VecDeque::from([(BookColumn::Title, ColumnSort::Ascending)]));
+/// assert_eq!(books.iter().map(|b| b.title.as_str()).collect::<Vec<_>>(), ["a", "b", "C"]);
+///
+/// sort_rows_by_column(&mut books, &VecDeque::from([(BookColumn::Year, ColumnSort::Descending)]));
+/// assert_eq!(books.iter().map(|b| b.title.as_str()).collect::<Vec<_>>(), ["C", "a", "b"]);
+/// ```
+pub trait SortableRow<Column> {
+    /// Compares two rows by `column` in **ascending** order. `sort` is the sort direction of the
+    /// column so `None` values can be placed independently of it.
+    fn compare_by(&self, other: &Self, column: Column, sort: ColumnSort) -> Ordering;
+}
+
+/// Sorts `rows` in place according to `sorting` with the comparison defined by [`SortableRow`].
+/// See [`sort_rows`] for details.
+pub fn sort_rows_by_column<Row, Column>(rows: &mut [Row], sorting: &VecDeque<(Column, ColumnSort)>)
+where
+    Row: SortableRow<Column>,
+    Column: Copy,
+{
+    sort_rows(rows, sorting, |a, b, column, sort| {
+        a.compare_by(b, column, sort)
+    });
+}
+
+/// Sorts `rows` in place according to `sorting` like it is provided to [`TableDataProvider::set_sorting`].
+///
+/// The first entry of `sorting` has the highest priority. The following entries are only used to
+/// order rows that are equal in all entries before. The sort is stable so rows that are equal in
+/// all sorted columns keep their order.
+///
+/// `compare` is called with two rows, the column and its sort direction and has to return the
+/// ordering of the two rows by that column in **ascending** order. Descending columns are reversed
+/// automatically. Use [`compare_values`] and [`compare_optional_values`] to implement it. The sort
+/// direction is passed along so `None` values can be placed independently of it.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_struct_table::*;
+/// # use std::collections::VecDeque;
+/// # use std::ops::Range;
+/// #
+/// #[derive(TableRow, Clone)]
+/// #[table(sortable, column_index_type = "enum")]
+/// pub struct Book {
+///     title: String,
+///     year: Option<u32>,
+/// }
+///
+/// pub struct BookDataProvider(Vec<Book>);
+///
+/// impl TableDataProvider<Book, BookColumn> for BookDataProvider {
+///     async fn get_rows(&self, range: Range<usize>) -> Result<(Vec<Book>, Range<usize>), String> {
+///         Ok(get_vec_range_clamped(&self.0, range))
+///     }
+///
+///     async fn row_count(&self) -> Option<usize> {
+///         Some(self.0.len())
+///     }
+///
+///     fn set_sorting(&mut self, sorting: &VecDeque<(BookColumn, ColumnSort)>) {
+///         sort_rows(&mut self.0, sorting, |a, b, column, sort| match column {
+///             // sort case insensitive
+///             BookColumn::Title => compare_values(&a.title.to_lowercase(), &b.title.to_lowercase()),
+///             // books without a year always come last
+///             BookColumn::Year => {
+///                 compare_optional_values(a.year.as_ref(), b.year.as_ref(), sort, NoneSortOrder::Last)
+///             }
+///         });
+///     }
+/// }
+/// ```
+pub fn sort_rows<Row, Column>(
+    rows: &mut [Row],
+    sorting: &VecDeque<(Column, ColumnSort)>,
+    compare: impl Fn(&Row, &Row, Column, ColumnSort) -> Ordering,
+) where
+    Column: Copy,
+{
+    if sorting.iter().all(|(_, sort)| *sort == ColumnSort::None) {
+        return;
+    }
+
+    rows.sort_by(|a, b| {
+        for (column, sort) in sorting {
+            let ordering = match sort {
+                ColumnSort::Ascending => compare(a, b, *column, *sort),
+                ColumnSort::Descending => compare(a, b, *column, *sort).reverse(),
+                ColumnSort::None => continue,
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        Ordering::Equal
+    });
+}
+
+/// Compares two values in ascending order. Values that can't be compared (like `NaN`) are treated as equal.
+/// To be used inside the `compare` function given to [`sort_rows`].
+pub fn compare_values<T: PartialOrd + ?Sized>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Compares two optional values in ascending order with the `None` values placed according to `none_order`.
+/// `sort` is the sort direction of the column as given to the `compare` function of [`sort_rows`].
+pub fn compare_optional_values<T: PartialOrd + ?Sized>(
+    a: Option<&T>,
+    b: Option<&T>,
+    sort: ColumnSort,
+    none_order: NoneSortOrder,
+) -> Ordering {
+    // `sort_rows` reverses the result for descending columns so we have to reverse it here
+    // as well if `None` should stay in place.
+    let none_ordering = match (none_order, sort) {
+        (NoneSortOrder::Smallest, _) => Ordering::Less,
+        (NoneSortOrder::First, ColumnSort::Descending) => Ordering::Greater,
+        (NoneSortOrder::First, _) => Ordering::Less,
+        (NoneSortOrder::Last, ColumnSort::Descending) => Ordering::Less,
+        (NoneSortOrder::Last, _) => Ordering::Greater,
+    };
+
+    match (a, b) {
+        (Some(a), Some(b)) => compare_values(a, b),
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => none_ordering,
+        (Some(_), None) => none_ordering.reverse(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Column {
+        Group,
+        Value,
+    }
+
+    /// (id, group, value)
+    type Row = (u8, &'static str, Option<i32>);
+
+    fn rows() -> Vec<Row> {
+        vec![
+            (0, "b", Some(2)),
+            (1, "a", None),
+            (2, "b", Some(1)),
+            (3, "a", Some(3)),
+            (4, "b", None),
+        ]
+    }
+
+    fn sorted_ids(sorting: &[(Column, ColumnSort)], none_order: NoneSortOrder) -> Vec<u8> {
+        let mut rows = rows();
+
+        sort_rows(
+            &mut rows,
+            &sorting.iter().copied().collect(),
+            |a, b, column, sort| match column {
+                Column::Group => compare_values(a.1, b.1),
+                Column::Value => {
+                    compare_optional_values(a.2.as_ref(), b.2.as_ref(), sort, none_order)
+                }
+            },
+        );
+
+        rows.into_iter().map(|(id, _, _)| id).collect()
+    }
+
+    #[test]
+    fn sorts_by_multiple_columns_in_priority_order() {
+        let sorting = [
+            (Column::Group, ColumnSort::Ascending),
+            (Column::Value, ColumnSort::Descending),
+        ];
+        assert_eq!(
+            sorted_ids(&sorting, NoneSortOrder::Last),
+            vec![3, 1, 0, 2, 4]
+        );
+
+        let sorting = [
+            (Column::Group, ColumnSort::Descending),
+            (Column::Value, ColumnSort::Ascending),
+        ];
+        assert_eq!(
+            sorted_ids(&sorting, NoneSortOrder::Last),
+            vec![2, 0, 4, 3, 1]
+        );
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let sorting = [(Column::Group, ColumnSort::Ascending)];
+        assert_eq!(
+            sorted_ids(&sorting, NoneSortOrder::Last),
+            vec![1, 3, 0, 2, 4]
+        );
+
+        let sorting = [(Column::Group, ColumnSort::Descending)];
+        assert_eq!(
+            sorted_ids(&sorting, NoneSortOrder::Last),
+            vec![0, 2, 4, 1, 3]
+        );
+    }
+
+    #[test]
+    fn unsorted_columns_keep_the_order() {
+        let sorting = [
+            (Column::Value, ColumnSort::None),
+            (Column::Group, ColumnSort::None),
+        ];
+        assert_eq!(
+            sorted_ids(&sorting, NoneSortOrder::Last),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(sorted_ids(&[], NoneSortOrder::Last), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn none_order() {
+        let ascending = [(Column::Value, ColumnSort::Ascending)];
+        let descending = [(Column::Value, ColumnSort::Descending)];
+
+        assert_eq!(
+            sorted_ids(&ascending, NoneSortOrder::First),
+            vec![1, 4, 2, 0, 3]
+        );
+        assert_eq!(
+            sorted_ids(&descending, NoneSortOrder::First),
+            vec![1, 4, 3, 0, 2]
+        );
+
+        assert_eq!(
+            sorted_ids(&ascending, NoneSortOrder::Last),
+            vec![2, 0, 3, 1, 4]
+        );
+        assert_eq!(
+            sorted_ids(&descending, NoneSortOrder::Last),
+            vec![3, 0, 2, 1, 4]
+        );
+
+        assert_eq!(
+            sorted_ids(&ascending, NoneSortOrder::Smallest),
+            vec![1, 4, 2, 0, 3]
+        );
+        assert_eq!(
+            sorted_ids(&descending, NoneSortOrder::Smallest),
+            vec![3, 0, 2, 1, 4]
+        );
+    }
+
+    #[test]
+    fn compare_optional_values_places_none() {
+        use ColumnSort::{Ascending, Descending};
+        use NoneSortOrder::{First, Last, Smallest};
+
+        let one = Some(&1);
+
+        assert_eq!(
+            compare_optional_values(one, Some(&2), Ascending, Last),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_optional_values::<i32>(None, None, Ascending, Last),
+            Ordering::Equal
+        );
+
+        // these are reversed by `sort_rows` for descending columns
+        assert_eq!(
+            compare_optional_values(None, one, Ascending, First),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_optional_values(None, one, Descending, First),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_optional_values(None, one, Ascending, Last),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_optional_values(None, one, Descending, Last),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_optional_values(None, one, Ascending, Smallest),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_optional_values(None, one, Descending, Smallest),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_optional_values(one, None, Descending, Last),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn compare_values_treats_incomparable_as_equal() {
+        assert_eq!(compare_values(&f64::NAN, &1.0), Ordering::Equal);
+        assert_eq!(compare_values("a", "b"), Ordering::Less);
+    }
+
+    impl SortableRow<Column> for Row {
+        fn compare_by(&self, other: &Self, column: Column, sort: ColumnSort) -> Ordering {
+            match column {
+                Column::Group => compare_values(self.1, other.1),
+                Column::Value => compare_optional_values(
+                    self.2.as_ref(),
+                    other.2.as_ref(),
+                    sort,
+                    NoneSortOrder::First,
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn sort_rows_by_column_uses_sortable_row() {
+        let mut rows = rows();
+        sort_rows_by_column(
+            &mut rows,
+            &VecDeque::from([
+                (Column::Group, ColumnSort::Descending),
+                (Column::Value, ColumnSort::Descending),
+            ]),
+        );
+
+        assert_eq!(
+            rows.into_iter().map(|(id, _, _)| id).collect::<Vec<_>>(),
+            vec![4, 0, 2, 1, 3]
+        );
+    }
+}
@@ -0,0 +1,819 @@
+use crate::{ColumnFilter, ColumnSort, FilterModel, FilterValue, TableRow};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// The SQL dialect used by [`SqlBuilder`] to quote identifiers and to write bind parameters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// Identifiers are quoted with `"` and parameters are written as `$1`, `$2`, ...
+    Postgres,
+    /// Identifiers are quoted with `` ` `` and parameters are written as `?`.
+    MySql,
+    /// Identifiers are quoted with `"` and parameters are written as `?`.
+    Sqlite,
+}
+
+impl SqlDialect {
+    /// Quotes the identifier so it can be safely used as a column or table name.
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        let quote = match self {
+            SqlDialect::Postgres | SqlDialect::Sqlite => '"',
+            SqlDialect::MySql => '`',
+        };
+
+        let mut quoted = String::with_capacity(identifier.len() + 2);
+        quoted.push(quote);
+        for c in identifier.chars() {
+            if c == quote {
+                quoted.push(quote);
+            }
+            quoted.push(c);
+        }
+        quoted.push(quote);
+
+        quoted
+    }
+
+    /// The placeholder for the bind parameter with the given 1-based position.
+    fn placeholder(&self, position: usize) -> String {
+        match self {
+            SqlDialect::Postgres => format!("${position}"),
+            SqlDialect::MySql | SqlDialect::Sqlite => "?".to_string(),
+        }
+    }
+
+    fn text_type(&self) -> &'static str {
+        match self {
+            SqlDialect::Postgres | SqlDialect::Sqlite => "TEXT",
+            SqlDialect::MySql => "CHAR",
+        }
+    }
+}
+
+/// How a table column is referred to in SQL. Returned by the function given to [`SqlBuilder::map_columns`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SqlColumn {
+    kind: SqlColumnKind,
+    sortable: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SqlColumnKind {
+    Name(&'static str),
+    Expression(&'static str),
+}
+
+impl SqlColumn {
+    /// Name of a database column. This is quoted by [`SqlBuilder`].
+    pub const fn name(name: &'static str) -> Self {
+        Self {
+            kind: SqlColumnKind::Name(name),
+            sortable: true,
+        }
+    }
+
+    /// SQL expression like `first_name || ' ' || last_name`. This is inserted into the SQL as is,
+    /// so it is checked to be a single expression: Statement separators and comments are rejected
+    /// as well as unbalanced parentheses and unterminated string literals or quoted identifiers.
+    pub fn expression(expression: &'static str) -> Result<Self, InvalidSqlExpression> {
+        check_expression(expression)?;
+
+        Ok(Self {
+            kind: SqlColumnKind::Expression(expression),
+            sortable: true,
+        })
+    }
+
+    /// The database can't sort by this column so it's ignored by [`SqlBuilder::order_by`].
+    /// This is independent of `skip_sort` so the column can still be sortable in the UI,
+    /// for example if the data provider sorts it itself.
+    pub const fn unsortable(mut self) -> Self {
+        self.sortable = false;
+        self
+    }
+
+    /// Returns `false` if this has been marked with [`Self::unsortable`].
+    pub const fn is_sortable(&self) -> bool {
+        self.sortable
+    }
+}
+
+/// Returned by [`SqlColumn::expression`] if the expression is not a single SQL expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidSqlExpression {
+    pub expression: &'static str,
+    pub reason: &'static str,
+}
+
+impl Display for InvalidSqlExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid SQL expression `{}`: {}",
+            self.expression, self.reason
+        )
+    }
+}
+
+impl std::error::Error for InvalidSqlExpression {}
+
+fn check_expression(expression: &'static str) -> Result<(), InvalidSqlExpression> {
+    let invalid = |reason| Err(InvalidSqlExpression { expression, reason });
+
+    if expression.trim().is_empty() {
+        return invalid("the expression is empty");
+    }
+
+    let mut quote = None;
+    let mut depth = 0_usize;
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            // quotes inside of literals and identifiers are escaped by doubling them
+            if c == q {
+                if chars.peek() == Some(&q) {
+                    chars.next();
+                } else {
+                    quote = None;
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '(' => depth += 1,
+            ')' => {
+                let Some(d) = depth.checked_sub(1) else {
+                    return invalid("unbalanced parentheses");
+                };
+                depth = d;
+            }
+            ';' => return invalid("statement separators are not allowed"),
+            '-' if chars.peek() == Some(&'-') => return invalid("comments are not allowed"),
+            '/' if chars.peek() == Some(&'*') => return invalid("comments are not allowed"),
+            _ => {}
+        }
+    }
+
+    if quote.is_some() {
+        return invalid("unterminated string literal or quoted identifier");
+    }
+    if depth > 0 {
+        return invalid("unbalanced parentheses");
+    }
+
+    Ok(())
+}
+
+/// SQL together with the values of its bind parameters in order. Built by [`SqlBuilder`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlFragment {
+    pub sql: String,
+    pub binds: Vec<FilterValue>,
+}
+
+/// Builds parameterized SQL for filtering, sorting and the requested row range of a table.
+///
+/// The database columns are named like the fields of the row ([`TableRow::col_name`]) unless
+/// they are mapped differently with [`Self::map_columns`]. Column names are quoted for the
+/// given [`SqlDialect`] and all values are passed as bind parameters so nothing is ever
+/// concatenated into the SQL. Bind the values of [`SqlFragment::binds`] in order with your
+/// database library.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_struct_table::*;
+/// # use std::collections::VecDeque;
+/// #
+/// #[derive(TableRow, Clone)]
+/// pub struct Customer {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let mut filter = FilterModel::default();
+/// filter.set(1, ColumnFilter::Range { min: Some(18.into()), max: None });
+///
+/// let sorting = VecDeque::from([(0, ColumnSort::Ascending)]);
+///
+/// let query = SqlBuilder::<Customer, usize>::new(SqlDialect::Postgres)
+///     .push_sql("SELECT * FROM customers")
+///     .filter(&filter)
+///     .order_by(&sorting)
+///     .range(20..30)
+///     .build();
+///
+/// assert_eq!(
+///     query.sql,
+///     r#"SELECT * FROM customers WHERE ("age" >= $1) ORDER BY "name" ASC LIMIT $2 OFFSET $3"#
+/// );
+/// assert_eq!(query.binds, vec![18.into(), 10.into(), 20.into()]);
+/// ```
+pub struct SqlBuilder<Row, Column> {
+    dialect: SqlDialect,
+    sql: String,
+    binds: Vec<FilterValue>,
+    /// The columns the global search is matched against. `None` means all columns.
+    search_columns: Option<Vec<Column>>,
+    case_sensitive: bool,
+    /// Whether a `WHERE` clause has been started so further conditions are appended with `AND`.
+    has_where: bool,
+    /// Set by [`Self::map_columns`]. `None` means the columns are named like the fields.
+    map_column: Option<Box<dyn Fn(Column) -> Option<SqlColumn>>>,
+    _marker: PhantomData<Row>,
+}
+
+impl<Row, Column> SqlBuilder<Row, Column>
+where
+    Row: TableRow<Column>,
+    Column: Copy + Send + Sync + 'static,
+{
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            dialect,
+            sql: String::new(),
+            binds: vec![],
+            search_columns: None,
+            case_sensitive: false,
+            has_where: false,
+            map_column: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Maps the columns of the table to database columns or SQL expressions. Return `None` for
+    /// columns that don't exist in the database. These are ignored by [`Self::order_by`], the
+    /// global search and column filters so the data provider has to handle them itself.
+    ///
+    /// By default every column maps to the database column named like the field ([`TableRow::col_name`]).
+    ///
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_struct_table::*;
+    /// # use std::collections::VecDeque;
+    /// #
+    /// #[derive(TableRow, Clone)]
+    /// #[table(column_index_type = "enum")]
+    /// pub struct Customer {
+    ///     name: String,
+    ///     age: u32,
+    ///     notes: String,
+    /// }
+    ///
+    /// let sorting = VecDeque::from([
+    ///     (CustomerColumn::Notes, ColumnSort::Ascending),
+    ///     (CustomerColumn::Name, ColumnSort::Ascending),
+    ///     (CustomerColumn::Age, ColumnSort::Descending),
+    /// ]);
+    ///
+    /// let name = SqlColumn::expression("first_name || ' ' || last_name").unwrap();
+    ///
+    /// let query = SqlBuilder::<Customer, CustomerColumn>::new(SqlDialect::Postgres)
+    ///     .map_columns(move |column| match column {
+    ///         CustomerColumn::Name => Some(name),
+    ///         CustomerColumn::Age => Some(SqlColumn::name("customer_age")),
+    ///         CustomerColumn::Notes => None,
+    ///     })
+    ///     .order_by(&sorting)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     query.sql,
+    ///     r#" ORDER BY (first_name || ' ' || last_name) ASC, "customer_age" DESC"#
+    /// );
+    /// ```
+    pub fn map_columns(
+        &mut self,
+        map: impl Fn(Column) -> Option<SqlColumn> + 'static,
+    ) -> &mut Self {
+        self.map_column = Some(Box::new(map));
+        self
+    }
+
+    /// Sets the columns that the global search of the [`FilterModel`] is matched against.
+    /// By default these are all columns of the row.
+    pub fn search_columns(&mut self, columns: &[Column]) -> &mut Self {
+        self.search_columns = Some(columns.to_vec());
+        self
+    }
+
+    /// If `true`, [`ColumnFilter::Contains`] and the global search use a plain `LIKE` so it depends
+    /// on the database and the collation of the column whether they are case sensitive.
+    /// By default both values are converted to lower case before comparing them.
+    pub fn case_sensitive(&mut self, case_sensitive: bool) -> &mut Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Appends raw SQL. Never pass user input to this, use [`Self::push_bind`] instead.
+    pub fn push_sql(&mut self, sql: &str) -> &mut Self {
+        self.sql.push_str(sql);
+        self
+    }
+
+    /// Appends a bind parameter placeholder and records its value.
+    pub fn push_bind(&mut self, value: impl Into<FilterValue>) -> &mut Self {
+        self.binds.push(value.into());
+        let placeholder = self.dialect.placeholder(self.binds.len());
+        self.sql.push_str(&placeholder);
+        self
+    }
+
+    /// Appends ` WHERE ...` for the column filters and the global search of `filter`.
+    /// The global search is matched against the columns set by [`Self::search_columns`].
+    /// Does nothing if `filter` is empty.
+    ///
+    /// Use [`Self::and_filter`] if the SQL given to [`Self::push_sql`] already has a `WHERE` clause.
+    pub fn filter(&mut self, filter: &FilterModel<Column>) -> &mut Self {
+        if filter.is_empty() {
+            return self;
+        }
+
+        for (column, column_filter) in &filter.columns {
+            let Some(sql_column) = self.sql_column(*column) else {
+                continue;
+            };
+
+            self.push_condition_start();
+            self.push_column_filter(sql_column, column_filter);
+            self.push_sql(")");
+        }
+
+        let search_columns = self
+            .search_columns
+            .clone()
+            .unwrap_or_else(|| Row::columns().to_vec())
+            .into_iter()
+            .filter_map(|column| self.sql_column(column))
+            .collect::<Vec<_>>();
+
+        if !filter.search.is_empty() && !search_columns.is_empty() {
+            self.push_condition_start();
+
+            for (i, column) in search_columns.into_iter().enumerate() {
+                if i > 0 {
+                    self.push_sql(" OR ");
+                }
+                self.push_contains(column, &filter.search);
+            }
+            self.push_sql(")");
+        }
+
+        self
+    }
+
+    /// Same as [`Self::filter`] but appends the conditions with ` AND ...` to a `WHERE` clause that
+    /// was added with [`Self::push_sql`].
+    ///
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_struct_table::*;
+    /// #
+    /// #[derive(TableRow, Clone)]
+    /// pub struct Customer {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut filter = FilterModel::default();
+    /// filter.set(1, ColumnFilter::Range { min: Some(18.into()), max: None });
+    ///
+    /// let query = SqlBuilder::<Customer, usize>::new(SqlDialect::Postgres)
+    ///     .push_sql("SELECT * FROM customers WHERE tenant_id = ")
+    ///     .push_bind(7)
+    ///     .and_filter(&filter)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     query.sql,
+    ///     r#"SELECT * FROM customers WHERE tenant_id = $1 AND ("age" >= $2)"#
+    /// );
+    /// ```
+    pub fn and_filter(&mut self, filter: &FilterModel<Column>) -> &mut Self {
+        self.has_where = true;
+        self.filter(filter)
+    }
+
+    /// Appends ` ORDER BY ...` for `sorting`. Does nothing if nothing is sorted.
+    /// Columns that are not mapped or [`SqlColumn::unsortable`] are ignored.
+    pub fn order_by(&mut self, sorting: &VecDeque<(Column, ColumnSort)>) -> &mut Self {
+        let mut first = true;
+
+        for (column, sort) in sorting {
+            let Some(sql_column) = self.sql_column(*column).filter(SqlColumn::is_sortable) else {
+                continue;
+            };
+
+            let Some(sort) = sort.as_sql() else {
+                continue;
+            };
+
+            self.push_sql(if first { " ORDER BY " } else { ", " });
+            first = false;
+
+            let column = self.column_sql(sql_column);
+            self.push_sql(&column).push_sql(" ").push_sql(sort);
+        }
+
+        self
+    }
+
+    /// Appends ` LIMIT ... OFFSET ...` for the rows in `range`.
+    pub fn range(&mut self, range: Range<usize>) -> &mut Self {
+        self.push_sql(" LIMIT ")
+            .push_bind(range.len() as i64)
+            .push_sql(" OFFSET ")
+            .push_bind(range.start as i64)
+    }
+
+    /// Returns the SQL and the bind values.
+    pub fn build(&mut self) -> SqlFragment {
+        SqlFragment {
+            sql: std::mem::take(&mut self.sql),
+            binds: std::mem::take(&mut self.binds),
+        }
+    }
+
+    /// Starts a parenthesized condition with ` WHERE (` or, if there already is a `WHERE` clause, ` AND (`.
+    fn push_condition_start(&mut self) {
+        let start = if self.has_where { " AND (" } else { " WHERE (" };
+        self.has_where = true;
+        self.push_sql(start);
+    }
+
+    fn sql_column(&self, column: Column) -> Option<SqlColumn> {
+        match &self.map_column {
+            Some(map_column) => map_column(column),
+            None => Some(SqlColumn::name(Row::col_name(column))),
+        }
+    }
+
+    fn column_sql(&self, column: SqlColumn) -> String {
+        match column.kind {
+            SqlColumnKind::Name(name) => self.dialect.quote_identifier(name),
+            SqlColumnKind::Expression(expression) => format!("({expression})"),
+        }
+    }
+
+    fn push_column_filter(&mut self, column: SqlColumn, filter: &ColumnFilter) {
+        let name = self.column_sql(column);
+
+        match filter {
+            ColumnFilter::Contains(text) => {
+                self.push_contains(column, text);
+            }
+            ColumnFilter::Equals(value) => {
+                self.push_sql(&name)
+                    .push_sql(" = ")
+                    .push_bind(value.clone());
+            }
+            ColumnFilter::Range { min, max } => {
+                if let Some(min) = min {
+                    self.push_sql(&name).push_sql(" >= ").push_bind(min.clone());
+                }
+                if min.is_some() && max.is_some() {
+                    self.push_sql(" AND ");
+                }
+                if let Some(max) = max {
+                    self.push_sql(&name).push_sql(" <= ").push_bind(max.clone());
+                }
+                if min.is_none() && max.is_none() {
+                    self.push_sql("1 = 1");
+                }
+            }
+            ColumnFilter::InSet(values) => {
+                if values.is_empty() {
+                    self.push_sql("1 = 0");
+                    return;
+                }
+
+                self.push_sql(&name).push_sql(" IN (");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        self.push_sql(", ");
+                    }
+                    self.push_bind(value.clone());
+                }
+                self.push_sql(")");
+            }
+        }
+    }
+
+    /// `LIKE` with the wildcards in `text` escaped. Case insensitive unless [`Self::case_sensitive`] is set.
+    fn push_contains(&mut self, column: SqlColumn, text: &str) {
+        let text = if self.case_sensitive {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        };
+
+        let mut pattern = String::with_capacity(text.len() + 2);
+        pattern.push('%');
+        for c in text.chars() {
+            if matches!(c, '!' | '%' | '_') {
+                pattern.push('!');
+            }
+            pattern.push(c);
+        }
+        pattern.push('%');
+
+        let column = self.column_sql(column);
+
+        if self.case_sensitive {
+            self.push_sql(&format!("{column} LIKE "));
+        } else {
+            let text_type = self.dialect.text_type();
+            self.push_sql(&format!("LOWER(CAST({column} AS {text_type})) LIKE "));
+        }
+
+        self.push_bind(pattern).push_sql(" ESCAPE '!'");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::prelude::*;
+
+    #[derive(Clone)]
+    pub struct Customer;
+
+    impl TableRow<usize> for Customer {
+        type ClassesProvider = crate::DummyTableClassesProvider;
+
+        const COLUMN_COUNT: usize = 3;
+
+        fn render_row(_: RwSignal<Self>, _: usize, _: RwSignal<Vec<usize>>) -> impl IntoView {}
+
+        fn render_head_row<F>(
+            _: Signal<VecDeque<(usize, ColumnSort)>>,
+            _: F,
+            _: crate::HeadDragHandler<usize>,
+            _: RwSignal<Vec<usize>>,
+        ) -> impl IntoView
+        where
+            F: Fn(crate::TableHeadEvent<usize>) + Send + Clone + 'static,
+        {
+        }
+
+        fn cell_renderer_for_column(_: RwSignal<Self>, _: usize, _: String) -> impl IntoView {}
+
+        fn col_name(column: usize) -> &'static str {
+            ["name", "company", "age"][column]
+        }
+
+        fn columns() -> &'static [usize] {
+            &[0, 1, 2]
+        }
+    }
+
+    fn builder(dialect: SqlDialect) -> SqlBuilder<Customer, usize> {
+        SqlBuilder::new(dialect)
+    }
+
+    fn filter(column: usize, filter: ColumnFilter) -> FilterModel<usize> {
+        let mut model = FilterModel::default();
+        model.set(column, filter);
+        model
+    }
+
+    #[test]
+    fn identifiers_are_quoted_for_the_dialect() {
+        assert_eq!(SqlDialect::Postgres.quote_identifier("name"), r#""name""#);
+        assert_eq!(SqlDialect::Sqlite.quote_identifier(r#"a"b"#), r#""a""b""#);
+        assert_eq!(SqlDialect::MySql.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(SqlDialect::MySql.quote_identifier(r#"a"b"#), r#"`a"b`"#);
+    }
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        let query = builder(SqlDialect::Postgres)
+            .filter(&filter(0, ColumnFilter::Contains("50%_OFF!".to_string())))
+            .build();
+
+        assert_eq!(
+            query.sql,
+            r#" WHERE (LOWER(CAST("name" AS TEXT)) LIKE $1 ESCAPE '!')"#
+        );
+        assert_eq!(query.binds, vec!["%50!%!_off!!%".into()]);
+    }
+
+    #[test]
+    fn empty_in_set_matches_nothing() {
+        let query = builder(SqlDialect::Sqlite)
+            .filter(&filter(0, ColumnFilter::InSet(vec![])))
+            .build();
+
+        assert_eq!(query.sql, " WHERE (1 = 0)");
+        assert!(query.binds.is_empty());
+
+        let query = builder(SqlDialect::Sqlite)
+            .filter(&filter(2, ColumnFilter::InSet(vec![1.into(), 2.into()])))
+            .build();
+
+        assert_eq!(query.sql, r#" WHERE ("age" IN (?, ?))"#);
+    }
+
+    #[test]
+    fn open_range_matches_everything() {
+        let query = builder(SqlDialect::Sqlite)
+            .filter(&filter(
+                2,
+                ColumnFilter::Range {
+                    min: None,
+                    max: None,
+                },
+            ))
+            .build();
+
+        assert_eq!(query.sql, " WHERE (1 = 1)");
+        assert!(query.binds.is_empty());
+
+        let query = builder(SqlDialect::Sqlite)
+            .filter(&filter(
+                2,
+                ColumnFilter::Range {
+                    min: None,
+                    max: Some(30.into()),
+                },
+            ))
+            .build();
+
+        assert_eq!(query.sql, r#" WHERE ("age" <= ?)"#);
+    }
+
+    #[test]
+    fn postgres_parameters_are_numbered_in_order() {
+        let mut model = filter(
+            2,
+            ColumnFilter::Range {
+                min: Some(18.into()),
+                max: Some(65.into()),
+            },
+        );
+        model.set(0, ColumnFilter::Equals("Ann".into()));
+        model.search = "x".to_string();
+
+        let query = builder(SqlDialect::Postgres)
+            .push_sql("SELECT * FROM customers")
+            .filter(&model)
+            .range(0..10)
+            .build();
+
+        assert_eq!(
+            query.sql,
+            concat!(
+                r#"SELECT * FROM customers WHERE ("age" >= $1 AND "age" <= $2) AND ("name" = $3) AND ("#,
+                r#"LOWER(CAST("name" AS TEXT)) LIKE $4 ESCAPE '!' OR "#,
+                r#"LOWER(CAST("company" AS TEXT)) LIKE $5 ESCAPE '!' OR "#,
+                r#"LOWER(CAST("age" AS TEXT)) LIKE $6 ESCAPE '!') LIMIT $7 OFFSET $8"#,
+            )
+        );
+        assert_eq!(query.binds.len(), 8);
+
+        let query = builder(SqlDialect::MySql).filter(&model).build();
+        assert!(!query.sql.contains('$'));
+        assert_eq!(query.sql.matches('?').count(), 6);
+    }
+
+    #[test]
+    fn and_filter_extends_an_existing_where_clause() {
+        let mut model = filter(0, ColumnFilter::Equals("Ann".into()));
+        model.search = "x".to_string();
+
+        let query = builder(SqlDialect::Sqlite)
+            .search_columns(&[1])
+            .push_sql("SELECT * FROM customers WHERE tenant_id = ")
+            .push_bind(7)
+            .and_filter(&model)
+            .build();
+
+        assert_eq!(
+            query.sql,
+            concat!(
+                r#"SELECT * FROM customers WHERE tenant_id = ? AND ("name" = ?) AND ("#,
+                r#"LOWER(CAST("company" AS TEXT)) LIKE ? ESCAPE '!')"#,
+            )
+        );
+        assert_eq!(query.binds, vec![7.into(), "Ann".into(), "%x%".into()]);
+
+        // an empty filter adds nothing
+        let query = builder(SqlDialect::Sqlite)
+            .push_sql("SELECT * FROM customers WHERE tenant_id = 7")
+            .and_filter(&FilterModel::default())
+            .build();
+        assert_eq!(query.sql, "SELECT * FROM customers WHERE tenant_id = 7");
+
+        // filtering twice doesn't start a second WHERE clause
+        let query = builder(SqlDialect::Sqlite)
+            .filter(&filter(0, ColumnFilter::Equals("Ann".into())))
+            .filter(&filter(1, ColumnFilter::Equals("Acme".into())))
+            .build();
+        assert_eq!(query.sql, r#" WHERE ("name" = ?) AND ("company" = ?)"#);
+    }
+
+    #[test]
+    fn search_columns_and_case_sensitivity() {
+        let model = FilterModel {
+            columns: vec![],
+            search: "Acme".to_string(),
+        };
+
+        let query = builder(SqlDialect::Sqlite)
+            .search_columns(&[1])
+            .case_sensitive(true)
+            .filter(&model)
+            .build();
+
+        assert_eq!(query.sql, r#" WHERE ("company" LIKE ? ESCAPE '!')"#);
+        assert_eq!(query.binds, vec!["%Acme%".into()]);
+    }
+
+    #[test]
+    fn order_by_uses_the_sort_priority() {
+        let sorting = VecDeque::from([
+            (2, ColumnSort::Descending),
+            (1, ColumnSort::None),
+            (0, ColumnSort::Ascending),
+        ]);
+
+        let query = builder(SqlDialect::MySql).order_by(&sorting).build();
+        assert_eq!(query.sql, " ORDER BY `age` DESC, `name` ASC");
+
+        let query = builder(SqlDialect::MySql)
+            .order_by(&VecDeque::new())
+            .build();
+        assert_eq!(query.sql, "");
+    }
+
+    #[test]
+    fn unmapped_columns_are_ignored() {
+        let mut model = filter(2, ColumnFilter::Equals(1.into()));
+        model.set(0, ColumnFilter::Equals("Ann".into()));
+        model.search = "x".to_string();
+
+        let query = builder(SqlDialect::Sqlite)
+            .map_columns(|column| match column {
+                0 => Some(SqlColumn::name("customer_name")),
+                1 => Some(SqlColumn::name("company").unsortable()),
+                _ => None,
+            })
+            .filter(&model)
+            .order_by(&VecDeque::from([
+                (2, ColumnSort::Ascending),
+                (1, ColumnSort::Ascending),
+                (0, ColumnSort::Descending),
+            ]))
+            .build();
+
+        assert_eq!(
+            query.sql,
+            concat!(
+                r#" WHERE ("customer_name" = ?) AND (LOWER(CAST("customer_name" AS TEXT)) LIKE ? ESCAPE '!' OR "#,
+                r#"LOWER(CAST("company" AS TEXT)) LIKE ? ESCAPE '!') ORDER BY "customer_name" DESC"#,
+            )
+        );
+
+        let query = builder(SqlDialect::Sqlite)
+            .map_columns(|_| None)
+            .filter(&model)
+            .build();
+        assert_eq!(query.sql, "");
+    }
+
+    #[test]
+    fn expressions_are_checked() {
+        let valid = [
+            "first_name || ' ' || last_name",
+            "COALESCE(\"nick name\", 'it''s me')",
+            "price * (1 - discount)",
+        ];
+        for expression in valid {
+            assert!(SqlColumn::expression(expression).is_ok(), "{expression}");
+        }
+
+        let invalid = [
+            "",
+            "name; DROP TABLE customers",
+            "name -- comment",
+            "name /* comment */",
+            "LOWER(name",
+            "name)",
+            "'unterminated",
+            "\"unterminated",
+        ];
+        for expression in invalid {
+            assert!(SqlColumn::expression(expression).is_err(), "{expression}");
+        }
+
+        let query = builder(SqlDialect::Postgres)
+            .map_columns(|_| SqlColumn::expression("a || b").ok())
+            .filter(&filter(0, ColumnFilter::Equals("x".into())))
+            .build();
+        assert_eq!(query.sql, " WHERE ((a || b) = $1)");
+    }
+}
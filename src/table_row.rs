@@ -51,6 +51,7 @@ pub trait TableRow<Column: Copy + Send + Sync + 'static>: Sized {
     /// Converts the given sorting to an SQL statement.
     /// Return `None` when there is nothing to be sorted otherwise `Some("ORDER BY ...")`.
     /// Uses [`Self::col_name`] to get the column names for sorting.
+    /// Use [`SqlBuilder`] if the database columns are named differently or need to be quoted.
     fn sorting_to_sql(sorting: &VecDeque<(Column, ColumnSort)>) -> Option<String>
     where
         Column: Send + Sync + 'static,
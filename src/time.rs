@@ -27,14 +27,16 @@ impl CellValue<Date> for Date {
     type RenderOptions = RenderTimeOptions;
 
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.string.as_ref() {
-            let format = format_description::parse_borrowed::<3>(value)
-                .expect("Unable to construct a format description given the format string");
-            self.format(&format)
-                .expect("Unable to format given the format description")
-        } else {
-            self.to_string()
-        }
+        self.format_text(&options)
+    }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+
+    #[cfg(feature = "xlsx")]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
     }
 }
 /// Implementation for [`Time`] to work with the [`TableRow`] derive and the [`DefaultTableCellRenderer`]
@@ -53,14 +55,16 @@ impl CellValue<Time> for Time {
     type RenderOptions = RenderTimeOptions;
 
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.string.as_ref() {
-            let format = format_description::parse_borrowed::<3>(value)
-                .expect("Unable to construct a format description given the format string");
-            self.format(&format)
-                .expect("Unable to format given the format description")
-        } else {
-            self.to_string()
-        }
+        self.format_text(&options)
+    }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+
+    #[cfg(feature = "xlsx")]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
     }
 }
 
@@ -80,14 +84,16 @@ impl CellValue<PrimitiveDateTime> for PrimitiveDateTime {
     type RenderOptions = RenderTimeOptions;
 
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.string.as_ref() {
-            let format = format_description::parse_borrowed::<3>(value)
-                .expect("Unable to construct a format description given the format string");
-            self.format(&format)
-                .expect("Unable to format given the format description")
-        } else {
-            self.to_string()
-        }
+        self.format_text(&options)
+    }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+
+    #[cfg(feature = "xlsx")]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
     }
 }
 
@@ -107,13 +113,36 @@ impl CellValue<OffsetDateTime> for OffsetDateTime {
     type RenderOptions = RenderTimeOptions;
 
     fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
-        if let Some(value) = options.string.as_ref() {
-            let format = format_description::parse_borrowed::<3>(value)
-                .expect("Unable to construct a format description given the format string");
-            self.format(&format)
-                .expect("Unable to format given the format description")
-        } else {
-            self.to_string()
-        }
+        self.format_text(&options)
     }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+
+    #[cfg(feature = "xlsx")]
+    fn to_xlsx_cell(&self, options: &Self::RenderOptions) -> Option<crate::xlsx::XlsxCell> {
+        Some(crate::xlsx::XlsxCellValue::xlsx_cell(self, options))
+    }
+}
+
+macro_rules! time_text_cell_value_impl {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TextCellValue<$ty> for $ty {
+                fn format_text(&self, options: &Self::RenderOptions) -> String {
+                    if let Some(value) = options.string.as_ref() {
+                        let format = format_description::parse_borrowed::<3>(value)
+                            .expect("Unable to construct a format description given the format string");
+                        self.format(&format)
+                            .expect("Unable to format given the format description")
+                    } else {
+                        self.to_string()
+                    }
+                }
+            }
+        )*
+    };
 }
+
+time_text_cell_value_impl![Date, Time, PrimitiveDateTime, OffsetDateTime];
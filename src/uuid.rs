@@ -17,7 +17,17 @@ use leptos::prelude::*;
 impl CellValue<Uuid> for Uuid {
     type RenderOptions = ();
 
-    fn render_value(self, _options: Self::RenderOptions) -> impl IntoView {
+    fn render_value(self, options: Self::RenderOptions) -> impl IntoView {
+        self.format_text(&options)
+    }
+
+    fn to_text(self, options: &Self::RenderOptions) -> Option<String> {
+        Some(self.format_text(options))
+    }
+}
+
+impl TextCellValue<Uuid> for Uuid {
+    fn format_text(&self, _options: &Self::RenderOptions) -> String {
         self.to_string()
     }
 }
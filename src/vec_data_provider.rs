@@ -0,0 +1,383 @@
+use crate::{
+    ColumnSort, FilterModel, FilterableRow, PaginatedTableDataProvider, SortableRow, TableRow,
+    sort_rows,
+};
+use leptos::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Data provider for a `Vec` of rows that filters and sorts them on the client.
+///
+/// The `Vec` provider generated by `#[table(impl_vec_data_provider)]` can only sort. Convert your
+/// `Vec` with `.into()` to get filtering and search as well.
+///
+/// The row type has to implement [`FilterableRow`] and [`SortableRow`]. The values returned by
+/// [`FilterableRow`] are used to apply the [`FilterModel`] given to the `filter` prop of
+/// [`TableContent`] and [`SortableRow`] defines how the rows are sorted.
+/// [`TableDataProvider::row_count`] returns the number of rows that pass the filter.
+///
+/// The row indices used by [`TableContent`] (for selection and in [`ChangeEvent::row_index`]) are
+/// the indices of the filtered and sorted rows. Use [`Self::original_index`] to map them back to
+/// the index of the row in the `Vec` this was created with. Clones share the same filtered
+/// indices so you can keep a clone around for that.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use std::cmp::Ordering;
+/// #
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum BookColumn {
+///     Title,
+///     Year,
+/// }
+///
+/// #[derive(Clone)]
+/// struct Book {
+///     title: String,
+///     year: Option<u16>,
+/// }
+///
+/// impl FilterableRow<BookColumn> for Book {
+///     fn filter_value(&self, column: BookColumn) -> Option<FilterValue> {
+///         match column {
+///             BookColumn::Title => Some(self.title.as_str().into()),
+///             BookColumn::Year => self.year.map(Into::into),
+///         }
+///     }
+/// }
+///
+/// impl SortableRow<BookColumn> for Book {
+///     fn compare_by(&self, other: &Self, column: BookColumn, sort: ColumnSort) -> Ordering {
+///         match column {
+///             BookColumn::Title => compare_values(&self.title, &other.title),
+///             BookColumn::Year => compare_optional_values(
+///                 self.year.as_ref(),
+///                 other.year.as_ref(),
+///                 sort,
+///                 NoneSortOrder::Last,
+///             ),
+///         }
+///     }
+/// }
+///
+/// let rows = VecDataProvider::<Book, BookColumn>::new(vec![
+///     Book { title: "The Hobbit".to_string(), year: Some(1937) },
+///     Book { title: "The Lord of the Rings".to_string(), year: Some(1954) },
+/// ]);
+///
+/// // pass a clone to `TableContent` and use `rows` to look up the original indices
+/// let _provider = rows.clone();
+///
+/// assert_eq!(rows.original_index(1), Some(1));
+/// ```
+pub struct VecDataProvider<Row, Column> {
+    rows: Arc<Vec<Row>>,
+    /// Indices into `rows` of the rows that pass the filter in sorted order.
+    indices: ArcRwSignal<Vec<usize>>,
+    sorting: VecDeque<(Column, ColumnSort)>,
+    filter: FilterModel<Column>,
+}
+
+impl<Row, Column: Clone> Clone for VecDataProvider<Row, Column> {
+    fn clone(&self) -> Self {
+        Self {
+            rows: Arc::clone(&self.rows),
+            indices: self.indices.clone(),
+            sorting: self.sorting.clone(),
+            filter: self.filter.clone(),
+        }
+    }
+}
+
+impl<Row, Column> VecDataProvider<Row, Column> {
+    pub fn new(rows: Vec<Row>) -> Self {
+        let indices = (0..rows.len()).collect();
+
+        Self {
+            rows: Arc::new(rows),
+            indices: ArcRwSignal::new(indices),
+            sorting: VecDeque::new(),
+            filter: FilterModel::default(),
+        }
+    }
+
+    /// All rows this was created with, unfiltered and unsorted.
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Returns the index in [`Self::rows`] of the row that is displayed at `row_index`.
+    /// Returns `None` if there is no row at that index after filtering.
+    pub fn original_index(&self, row_index: usize) -> Option<usize> {
+        self.indices
+            .with_untracked(|indices| indices.get(row_index).copied())
+    }
+
+    /// The number of rows that pass the filter. This is reactive.
+    pub fn filtered_row_count(&self) -> usize {
+        self.indices.with(Vec::len)
+    }
+}
+
+impl<Row, Column> From<Vec<Row>> for VecDataProvider<Row, Column> {
+    fn from(rows: Vec<Row>) -> Self {
+        Self::new(rows)
+    }
+}
+
+impl<Row, Column> VecDataProvider<Row, Column>
+where
+    Row: FilterableRow<Column> + SortableRow<Column> + TableRow<Column>,
+    Column: Copy + Send + Sync + 'static,
+{
+    fn update_indices(&self) {
+        let mut indices = (0..self.rows.len())
+            .filter(|index| self.filter.matches(&self.rows[*index], Row::columns()))
+            .collect::<Vec<_>>();
+
+        sort_rows(&mut indices, &self.sorting, |a, b, column, sort| {
+            self.rows[*a].compare_by(&self.rows[*b], column, sort)
+        });
+
+        self.indices.set(indices);
+    }
+}
+
+impl<Row, Column> PaginatedTableDataProvider<Row, Column> for VecDataProvider<Row, Column>
+where
+    Row: FilterableRow<Column> + SortableRow<Column> + TableRow<Column> + Clone,
+    Column: Copy + Send + Sync + 'static,
+{
+    const PAGE_ROW_COUNT: usize = 100;
+
+    async fn get_page(&self, page_index: usize) -> Result<Vec<Row>, String> {
+        let start = page_index * Self::PAGE_ROW_COUNT;
+
+        self.indices.with_untracked(|indices| {
+            Ok(indices
+                .iter()
+                .skip(start)
+                .take(Self::PAGE_ROW_COUNT)
+                .map(|index| self.rows[*index].clone())
+                .collect())
+        })
+    }
+
+    async fn row_count(&self) -> Option<usize> {
+        Some(self.indices.with_untracked(Vec::len))
+    }
+
+    fn set_sorting(&mut self, sorting: &VecDeque<(Column, ColumnSort)>) {
+        self.sorting = sorting.clone();
+        self.update_indices();
+    }
+
+    fn set_filter(&mut self, filter: &FilterModel<Column>) {
+        self.filter = filter.clone();
+        self.update_indices();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ColumnFilter, FilterValue, NoneSortOrder, compare_optional_values, compare_values,
+    };
+    use std::cmp::Ordering;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Book {
+        title: &'static str,
+        year: Option<u16>,
+    }
+
+    impl TableRow<usize> for Book {
+        type ClassesProvider = crate::DummyTableClassesProvider;
+
+        const COLUMN_COUNT: usize = 2;
+
+        fn render_row(_: RwSignal<Self>, _: usize, _: RwSignal<Vec<usize>>) -> impl IntoView {}
+
+        fn render_head_row<F>(
+            _: Signal<VecDeque<(usize, ColumnSort)>>,
+            _: F,
+            _: crate::HeadDragHandler<usize>,
+            _: RwSignal<Vec<usize>>,
+        ) -> impl IntoView
+        where
+            F: Fn(crate::TableHeadEvent<usize>) + Send + Clone + 'static,
+        {
+        }
+
+        fn cell_renderer_for_column(_: RwSignal<Self>, _: usize, _: String) -> impl IntoView {}
+
+        fn col_name(column: usize) -> &'static str {
+            ["title", "year"][column]
+        }
+
+        fn columns() -> &'static [usize] {
+            &[0, 1]
+        }
+    }
+
+    impl FilterableRow<usize> for Book {
+        fn filter_value(&self, column: usize) -> Option<FilterValue> {
+            match column {
+                0 => Some(self.title.into()),
+                _ => self.year.map(Into::into),
+            }
+        }
+    }
+
+    impl SortableRow<usize> for Book {
+        fn compare_by(&self, other: &Self, column: usize, sort: ColumnSort) -> Ordering {
+            match column {
+                0 => compare_values(self.title, other.title),
+                _ => compare_optional_values(
+                    self.year.as_ref(),
+                    other.year.as_ref(),
+                    sort,
+                    NoneSortOrder::Last,
+                ),
+            }
+        }
+    }
+
+    fn provider() -> VecDataProvider<Book, usize> {
+        vec![
+            Book {
+                title: "The Lord of the Rings",
+                year: Some(1954),
+            },
+            Book {
+                title: "The Silmarillion",
+                year: None,
+            },
+            Book {
+                title: "The Hobbit",
+                year: Some(1937),
+            },
+            Book {
+                title: "Unfinished Tales",
+                year: Some(1980),
+            },
+        ]
+        .into()
+    }
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!(),
+        }
+    }
+
+    fn titles(provider: &VecDataProvider<Book, usize>) -> Vec<&'static str> {
+        block_on(provider.get_page(0))
+            .unwrap()
+            .into_iter()
+            .map(|book| book.title)
+            .collect()
+    }
+
+    fn original_indices(provider: &VecDataProvider<Book, usize>) -> Vec<usize> {
+        (0..provider.filtered_row_count())
+            .map(|index| provider.original_index(index).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn column_filter_reduces_row_count() {
+        let _owner = Owner::new();
+        let mut provider = provider();
+        assert_eq!(block_on(provider.row_count()), Some(4));
+
+        let mut filter = FilterModel::default();
+        filter.set(
+            1,
+            ColumnFilter::Range {
+                min: Some(1950.into()),
+                max: None,
+            },
+        );
+        provider.set_filter(&filter);
+
+        assert_eq!(block_on(provider.row_count()), Some(2));
+        assert_eq!(
+            titles(&provider),
+            vec!["The Lord of the Rings", "Unfinished Tales"]
+        );
+        assert_eq!(original_indices(&provider), vec![0, 3]);
+        assert_eq!(provider.original_index(2), None);
+
+        provider.set_filter(&FilterModel::default());
+        assert_eq!(block_on(provider.row_count()), Some(4));
+    }
+
+    #[test]
+    fn search_matches_all_columns() {
+        let _owner = Owner::new();
+        let mut provider = provider();
+
+        provider.set_filter(&FilterModel {
+            columns: vec![],
+            search: "the".to_string(),
+        });
+        assert_eq!(original_indices(&provider), vec![0, 1, 2]);
+
+        provider.set_filter(&FilterModel {
+            columns: vec![],
+            search: "1937".to_string(),
+        });
+        assert_eq!(titles(&provider), vec!["The Hobbit"]);
+    }
+
+    #[test]
+    fn sorting_maps_to_original_indices() {
+        let _owner = Owner::new();
+        let mut provider = provider();
+
+        provider.set_sorting(&VecDeque::from([(1, ColumnSort::Descending)]));
+        assert_eq!(original_indices(&provider), vec![3, 0, 2, 1]);
+
+        provider.set_sorting(&VecDeque::from([(0, ColumnSort::Ascending)]));
+        assert_eq!(
+            titles(&provider),
+            vec![
+                "The Hobbit",
+                "The Lord of the Rings",
+                "The Silmarillion",
+                "Unfinished Tales"
+            ]
+        );
+        assert_eq!(original_indices(&provider), vec![2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn filter_and_sorting_are_combined() {
+        let _owner = Owner::new();
+        let mut provider = provider();
+
+        provider.set_sorting(&VecDeque::from([(1, ColumnSort::Ascending)]));
+        let mut filter = FilterModel::default();
+        filter.set(0, ColumnFilter::Contains("the".to_string()));
+        provider.set_filter(&filter);
+
+        assert_eq!(original_indices(&provider), vec![2, 0, 1]);
+        // the rows are never reordered
+        assert_eq!(provider.rows()[0].title, "The Lord of the Rings");
+    }
+
+    #[test]
+    fn clones_share_the_indices() {
+        let _owner = Owner::new();
+        let provider = provider();
+        let mut table_provider = provider.clone();
+
+        table_provider.set_sorting(&VecDeque::from([(1, ColumnSort::Descending)]));
+        assert_eq!(provider.original_index(0), Some(3));
+    }
+}
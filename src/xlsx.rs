@@ -0,0 +1,1049 @@
+//! Export to Excel files with typed cells using the crate [::rust_xlsxwriter].
+//!
+//! Use an [`XlsxExporter`] to export all rows of a table through its [`RowReader`]. The values are
+//! written as numbers, booleans, dates and times so they can be calculated with in the spreadsheet.
+//! Their number and date formats are derived from the options of `#[table(format(...))]`, so they look
+//! like in the table (see [`XlsxCellValue`]).
+
+use crate::clipboard::{
+    CellReader, CellsFn, ProbedCells, RowCells, cells_of_fn, cells_of_renderers,
+};
+use crate::csv_export::{ExportOptions, export_columns, export_rows};
+use crate::number_format::{currency_affixes, currency_digits};
+use crate::*;
+use ::rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+use leptos::prelude::{SetValue, Signal, StoredValue, WithUntracked};
+use std::sync::Arc;
+
+pub use ::rust_xlsxwriter;
+
+/// Excel can only display numbers with up to this many digits after the decimal point.
+const MAX_PRECISION: usize = 30;
+
+/// Excel stores numbers with 15 significant digits, so more digits after the decimal point are never shown
+/// without a `precision`.
+const OPTIONAL_DECIMALS: usize = 15;
+
+/// A typed spreadsheet cell.
+#[derive(Clone, Default)]
+pub enum XlsxCell {
+    #[default]
+    Empty,
+    Text(String),
+    Boolean(bool),
+    /// A number with an optional Excel number format like `"0.00"`. Without a format Excel uses "General".
+    Number {
+        value: f64,
+        num_format: Option<String>,
+    },
+    /// A date, time or date and time with an Excel number format like `"yyyy-mm-dd"`.
+    DateTime {
+        value: ExcelDateTime,
+        num_format: String,
+    },
+}
+
+impl XlsxCell {
+    /// Writes this cell to the `worksheet`.
+    pub fn write(&self, worksheet: &mut Worksheet, row: u32, col: u16) -> Result<(), XlsxError> {
+        match self {
+            XlsxCell::Empty => {}
+            XlsxCell::Text(text) => {
+                worksheet.write_string(row, col, text)?;
+            }
+            XlsxCell::Boolean(value) => {
+                worksheet.write_boolean(row, col, *value)?;
+            }
+            XlsxCell::Number {
+                value,
+                num_format: None,
+            } => {
+                worksheet.write_number(row, col, *value)?;
+            }
+            XlsxCell::Number {
+                value,
+                num_format: Some(num_format),
+            } => {
+                let format = Format::new().set_num_format(num_format);
+                worksheet.write_number_with_format(row, col, *value, &format)?;
+            }
+            XlsxCell::DateTime { value, num_format } => {
+                let format = Format::new().set_num_format(num_format);
+                worksheet.write_datetime_with_format(row, col, value, &format)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A date or time cell. Falls back to the text returned by `fallback` if the value can't be
+    /// represented in Excel, for example because the year is before 1900.
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    fn date_time(
+        value: Result<ExcelDateTime, XlsxError>,
+        num_format: String,
+        fallback: impl FnOnce() -> String,
+    ) -> Self {
+        match value {
+            Ok(value) => XlsxCell::DateTime { value, num_format },
+            Err(_) => XlsxCell::Text(fallback()),
+        }
+    }
+}
+
+impl From<Option<XlsxCell>> for XlsxCell {
+    fn from(cell: Option<XlsxCell>) -> Self {
+        cell.unwrap_or_default()
+    }
+}
+
+/// A [`CellValue`] that can be written to a spreadsheet cell.
+///
+/// The cell type and the number format are derived from the same `RenderOptions` that
+/// `#[table(format(...))]` uses, so the exported cells look like the rendered ones.
+pub trait XlsxCellValue<M: ?Sized = ()>: CellValue<M> {
+    /// Returns the typed cell for this value.
+    fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell;
+}
+
+/// Implement this for your row type to override the typed cells that are exported by an [`XlsxExporter`].
+///
+/// This isn't needed for rows that `#[derive(TableRow)]`. By default the cell is derived from the value and
+/// the options of `#[table(format(...))]` (see [`XlsxCellGetter::from_cell_renderers`]). Only cells with a
+/// custom renderer are empty. Use [`xlsx_cell_of`] for the other columns.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos_struct_table::xlsx::*;
+/// # use leptos::prelude::*;
+/// #[derive(TableRow, Clone)]
+/// #[table]
+/// struct Invoice {
+///     customer: String,
+///     #[table(format(precision = 2usize))]
+///     amount: f64,
+///     #[table(renderer = "TagsCellRenderer")]
+///     tags: Vec<String>,
+/// }
+///
+/// impl XlsxRow<usize> for Invoice {
+///     fn xlsx_cell(&self, column: usize) -> XlsxCell {
+///         match column {
+///             2 => XlsxCell::Text(self.tags.join(", ")),
+///             column => xlsx_cell_of(self, column),
+///         }
+///     }
+/// }
+/// #
+/// # #[component]
+/// # fn TagsCellRenderer(
+/// #     class: String,
+/// #     value: Signal<Vec<String>>,
+/// #     row: RwSignal<Invoice>,
+/// #     index: usize,
+/// # ) -> impl IntoView {
+/// #     view! { <td class=class>{move || value.get().join(", ")}</td> }
+/// # }
+/// ```
+pub trait XlsxRow<Column> {
+    /// Returns the typed cell of the given column.
+    fn xlsx_cell(&self, column: Column) -> XlsxCell;
+}
+
+/// Returns the typed cell of a row for an [`XlsxExporter`].
+pub struct XlsxCellGetter<Row, Column>(Arc<CellsFn<Row, Column, XlsxCell>>);
+
+impl<Row, Column> Clone for XlsxCellGetter<Row, Column> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<F, Row, Column> From<F> for XlsxCellGetter<Row, Column>
+where
+    F: Fn(&Row, Column) -> XlsxCell + Send + Sync + 'static,
+    Row: 'static,
+    Column: Copy + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(cells_of_fn(f))
+    }
+}
+
+impl<Row, Column> XlsxCellGetter<Row, Column> {
+    /// Uses the cell renderers to get the typed cells like [`xlsx_cell_of`]. This is the default.
+    ///
+    /// The cells of a column are only built once per export and then reused for every row.
+    pub fn from_cell_renderers() -> Self
+    where
+        Row: TableRow<Column> + Clone + Send + Sync + 'static,
+        Column: Copy + Send + Sync + 'static,
+    {
+        Self(cells_of_renderers(XlsxCellProbe))
+    }
+
+    /// Uses [`XlsxRow::xlsx_cell`] to get the typed cell.
+    pub fn from_xlsx_row() -> Self
+    where
+        Row: XlsxRow<Column> + 'static,
+        Column: Copy + 'static,
+    {
+        Self(cells_of_fn(Row::xlsx_cell))
+    }
+
+    /// Returns the typed cell of a single cell. The exporter gets the cells of many rows at once.
+    pub fn run(&self, row: &Row, column: Column) -> XlsxCell {
+        (self.0)(&[column])(row).pop().unwrap_or_default()
+    }
+
+    pub(crate) fn cells(&self, columns: &[Column]) -> RowCells<Row, XlsxCell> {
+        (self.0)(columns)
+    }
+}
+
+/// Provided as context while the cells are built for [`XlsxCellGetter::from_cell_renderers`]. The
+/// [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()) writes how to get the typed cell of its
+/// value into it.
+#[derive(Copy, Clone)]
+pub(crate) struct XlsxCellProbe(StoredValue<Option<CellReader<XlsxCell>>>);
+
+impl XlsxCellProbe {
+    pub(crate) fn set<T, M>(&self, value: Signal<T>, options: T::RenderOptions)
+    where
+        T: CellValue<M> + Clone + Send + Sync + 'static,
+        M: ?Sized,
+    {
+        self.0.set_value(Some(Arc::new(move || {
+            value.with_untracked(|value| {
+                value
+                    .to_xlsx_cell(&options)
+                    .or_else(|| value.clone().to_text(&options).map(XlsxCell::Text))
+            })
+        })));
+    }
+}
+
+/// Returns the typed cell of `column` with the number or date format of the options given to
+/// `#[table(format(...))]` (see [`CellValue::to_xlsx_cell`]). Values without a typed cell are written as
+/// their text. The cell is built with [`TableRow::cell_renderer_for_column`] but never rendered.
+/// Returns [`XlsxCell::Empty`] if the cell isn't rendered by the
+/// [`DefaultTableCellRenderer`](crate::DefaultTableCellRenderer()).
+pub fn xlsx_cell_of<Row, Column>(row: &Row, column: Column) -> XlsxCell
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    ProbedCells::new(row, &[column], XlsxCellProbe)
+        .read()
+        .next()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Exports all rows of a table to an Excel file with typed cells.
+///
+/// By default the columns that the table shows are exported (see [`RowReader::columns`]) with
+/// [`TableRow::col_name`] as titles in a bold and frozen first row. The cells are typed like their values
+/// in the table (see [`XlsxCellGetter::from_cell_renderers`]). Like [`CsvExporter`] the rows are requested
+/// chunk by chunk with the sorting and filter of the table, and the export reports its progress, can be
+/// cancelled with an [`AbortToken`] and is stopped with [`ExportError::TableChanged`] if the sorting or
+/// filter changes.
+///
+/// ```
+/// # use leptos_struct_table::*;
+/// # use leptos_struct_table::xlsx::*;
+/// # use leptos::prelude::*;
+/// #[derive(TableRow, Clone)]
+/// #[table]
+/// struct Book {
+///     title: String,
+///     pages: u32,
+/// }
+///
+/// async fn export(row_reader: RowReader<Book>) -> Vec<u8> {
+///     let mut exporter = XlsxExporter::<Book, usize>::new();
+///
+///     exporter
+///         .header(vec!["Title".to_string(), "Pages".to_string()])
+///         .worksheet_name("Books");
+///
+///     exporter.export_to_buffer(&row_reader).await.unwrap()
+/// }
+/// ```
+pub struct XlsxExporter<Row, Column> {
+    columns: Option<Vec<Column>>,
+    header: Option<Vec<String>>,
+    worksheet_name: Option<String>,
+    cells: XlsxCellGetter<Row, Column>,
+    options: ExportOptions,
+}
+
+impl<Row, Column> Default for XlsxExporter<Row, Column>
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Row, Column> XlsxExporter<Row, Column>
+where
+    Row: TableRow<Column> + Clone + Send + Sync + 'static,
+    Column: Copy + Send + Sync + 'static,
+{
+    /// Exports the columns of the table, or all columns of [`TableRow::columns`] if it hasn't been rendered.
+    pub fn new() -> Self {
+        Self {
+            columns: None,
+            header: None,
+            worksheet_name: None,
+            cells: XlsxCellGetter::from_cell_renderers(),
+            options: ExportOptions::default(),
+        }
+    }
+
+    /// Exports only the given `columns` in this order instead of the columns of the table.
+    pub fn columns(&mut self, columns: Vec<Column>) -> &mut Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Uses these column titles instead of [`TableRow::col_name`] in the first row.
+    pub fn header(&mut self, titles: Vec<String>) -> &mut Self {
+        self.header = Some(titles);
+        self
+    }
+
+    /// Overrides the typed cells, for example with [`XlsxCellGetter::from_xlsx_row`].
+    pub fn cells(&mut self, cells: impl Into<XlsxCellGetter<Row, Column>>) -> &mut Self {
+        self.cells = cells.into();
+        self
+    }
+
+    /// The name of the worksheet that is created by [`Self::export_to_buffer`].
+    pub fn worksheet_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.worksheet_name = Some(name.into());
+        self
+    }
+
+    /// Call [`AbortToken::abort`] on this token to cancel the export. It's also passed to
+    /// [`TableDataProvider::get_rows_with_context`].
+    pub fn abort_token(&mut self, abort_token: AbortToken) -> &mut Self {
+        self.options.abort_token = abort_token;
+        self
+    }
+
+    /// Called after every chunk of rows has been written.
+    pub fn on_progress(&mut self, on_progress: impl Fn(ExportProgress) + 'static) -> &mut Self {
+        self.options.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Requests all rows of the table of `rows` and writes them to `worksheet` starting at the first row.
+    /// Returns the number of exported rows.
+    pub async fn export_to_worksheet(
+        &self,
+        rows: &RowReader<Row, Column>,
+        worksheet: &mut Worksheet,
+    ) -> Result<usize, ExportError> {
+        let columns =
+            export_columns(&self.columns, rows).unwrap_or_else(|| Row::columns().to_vec());
+        let header_format = Format::new().set_bold();
+
+        for (col, column) in columns.iter().enumerate() {
+            let title = match &self.header {
+                Some(header) => header.get(col).map(String::as_str).unwrap_or_default(),
+                None => Row::col_name(*column),
+            };
+            worksheet.write_string_with_format(0, col as u16, title, &header_format)?;
+        }
+        worksheet.set_freeze_panes(1, 0)?;
+
+        let mut row_index = 1;
+
+        let mut row_cells = self.cells.cells(&columns);
+
+        let exported_rows = export_rows(rows, &self.options, |row: &Row| {
+            let cells = rows.with_table_owner(|| row_cells(row));
+
+            for (col, cell) in cells.iter().enumerate() {
+                cell.write(worksheet, row_index, col as u16)?;
+            }
+            row_index += 1;
+
+            Ok(())
+        })
+        .await?;
+
+        Ok(exported_rows)
+    }
+
+    /// Same as [`Self::export_to_worksheet`] but creates a workbook with one worksheet and
+    /// returns the content of the `.xlsx` file.
+    pub async fn export_to_buffer(
+        &self,
+        rows: &RowReader<Row, Column>,
+    ) -> Result<Vec<u8>, ExportError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        if let Some(name) = &self.worksheet_name {
+            worksheet.set_name(name)?;
+        }
+
+        self.export_to_worksheet(rows, worksheet).await?;
+
+        Ok(workbook.save_to_buffer()?)
+    }
+}
+
+impl From<XlsxError> for ExportError {
+    fn from(err: XlsxError) -> Self {
+        Self::Xlsx(err)
+    }
+}
+
+/// The Excel number format that matches the [`NumberRenderOptions`]. Excel shows it with the separators
+/// of the user's locale. Significant digits and the compact notation can't be expressed so the full number
+/// is shown. Without a `precision` the digits after the decimal point of non-`integer` values are kept
+/// as they are. Like in the table integers aren't padded with zeros by the `precision`.
+fn number_num_format(options: &NumberRenderOptions, integer: bool) -> Option<String> {
+    let locale = options.locale.unwrap_or_default();
+    let grouping = options.thousands_separator.unwrap_or(locale.use_grouping);
+    let percent = options.percent.unwrap_or_default();
+    let sign_display = options.sign_display.unwrap_or_default();
+    let precision = match options.precision {
+        Some(_) if integer => Some(0),
+        precision => precision,
+    }
+    .or_else(|| options.currency.map(currency_digits));
+
+    if precision.is_none()
+        && !grouping
+        && !percent
+        && sign_display == SignDisplay::Auto
+        && options.currency.is_none()
+        && options.unit.is_none()
+    {
+        return None;
+    }
+
+    let (currency_prefix, currency_suffix) = options
+        .currency
+        .map(|currency| currency_affixes(currency, &locale))
+        .unwrap_or_default();
+
+    let mut num_format = quoted_literal(&currency_prefix);
+    num_format.push_str(if grouping { "#,##0" } else { "0" });
+    match precision {
+        Some(precision) if precision > 0 => {
+            num_format.push('.');
+            num_format.push_str(&"0".repeat(precision.min(MAX_PRECISION)));
+        }
+        None if !integer => {
+            num_format.push('.');
+            num_format.push_str(&"#".repeat(OPTIONAL_DECIMALS));
+        }
+        _ => {}
+    }
+    if percent {
+        num_format.push('%');
+    }
+    num_format.push_str(&quoted_literal(&currency_suffix));
+    if let Some(unit) = options.unit {
+        num_format.push_str(&quoted_literal(&format!("\u{a0}{unit}")));
+    }
+
+    Some(match sign_display {
+        SignDisplay::Auto => num_format,
+        SignDisplay::Always => format!("+{num_format};-{num_format};+{num_format}"),
+        SignDisplay::ExceptZero => format!("+{num_format};-{num_format};{num_format}"),
+        SignDisplay::Never => format!("{num_format};{num_format};{num_format}"),
+    })
+}
+
+/// Quotes text so Excel shows it as it is in a number format. Quotes in the text are doubled.
+fn quoted_literal(text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    }
+}
+
+macro_rules! xlsx_number_impl {
+    ($integer:literal: $($ty:ty),* $(,)?) => {
+        $(
+            impl XlsxCellValue<$ty> for $ty {
+                fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+                    XlsxCell::Number {
+                        value: *self as f64,
+                        num_format: number_num_format(options, $integer),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+xlsx_number_impl![
+    true: usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128,
+];
+xlsx_number_impl![false: f32, f64];
+
+impl XlsxCellValue<bool> for bool {
+    fn xlsx_cell(&self, _options: &Self::RenderOptions) -> XlsxCell {
+        XlsxCell::Boolean(*self)
+    }
+}
+
+impl XlsxCellValue<char> for char {
+    fn xlsx_cell(&self, _options: &Self::RenderOptions) -> XlsxCell {
+        XlsxCell::Text(self.to_string())
+    }
+}
+
+impl XlsxCellValue for String {
+    fn xlsx_cell(&self, _options: &Self::RenderOptions) -> XlsxCell {
+        XlsxCell::Text(self.clone())
+    }
+}
+
+impl XlsxCellValue for &'static str {
+    fn xlsx_cell(&self, _options: &Self::RenderOptions) -> XlsxCell {
+        XlsxCell::Text(self.to_string())
+    }
+}
+
+/// Appends a character that Excel would interpret as part of the format as a quoted literal.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn push_literal(num_format: &mut String, c: char) {
+    if c.is_ascii_alphanumeric() || c == '"' || c == '\\' {
+        num_format.push('\\');
+    }
+    num_format.push(c);
+}
+
+/// Converts a [`::chrono::format::strftime`] format string to an Excel number format.
+/// Returns `None` if it contains a specifier that Excel can't display.
+#[cfg(feature = "chrono")]
+fn strftime_to_num_format(format: &str) -> Option<String> {
+    let mut num_format = String::new();
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            push_literal(&mut num_format, c);
+            continue;
+        }
+
+        let mut specifier = chars.next()?;
+        let unpadded = matches!(specifier, '-' | '_');
+        if unpadded || specifier == '0' {
+            specifier = chars.next()?;
+        }
+
+        let pair = |padded: &'static str, unpadded_value: &'static str| {
+            if unpadded { unpadded_value } else { padded }
+        };
+
+        let part = match specifier {
+            'Y' => "yyyy",
+            'y' => "yy",
+            'm' => pair("mm", "m"),
+            'b' | 'h' => "mmm",
+            'B' => "mmmm",
+            'd' => pair("dd", "d"),
+            'e' => "d",
+            'a' => "ddd",
+            'A' => "dddd",
+            'H' | 'I' => pair("hh", "h"),
+            'k' | 'l' => "h",
+            'M' => pair("mm", "m"),
+            'S' => pair("ss", "s"),
+            'p' | 'P' => "AM/PM",
+            'F' => "yyyy-mm-dd",
+            'D' | 'x' => "mm/dd/yy",
+            'T' | 'X' => "hh:mm:ss",
+            'R' => "hh:mm",
+            '%' => "%",
+            '.' => match chars.next()? {
+                'f' | '3' => ".000",
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        num_format.push_str(part);
+    }
+
+    Some(num_format)
+}
+
+/// Converts a [`::time`] format description to an Excel number format.
+/// Returns `None` if it contains a component that Excel can't display.
+#[cfg(feature = "time")]
+fn time_description_to_num_format(description: &str) -> Option<String> {
+    let mut num_format = String::new();
+    let mut chars = description.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '[' {
+            push_literal(&mut num_format, c);
+            continue;
+        }
+
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            push_literal(&mut num_format, '[');
+            continue;
+        }
+
+        let mut component = String::new();
+        for c in chars.by_ref() {
+            if c == ']' {
+                break;
+            }
+            component.push(c);
+        }
+
+        let mut parts = component.split_whitespace();
+        let name = parts.next()?;
+        let modifiers = parts.collect::<Vec<_>>();
+        let has = |modifier: &str| modifiers.contains(&modifier);
+        let padded = |padded: &'static str, unpadded: &'static str| {
+            if has("padding:none") {
+                unpadded
+            } else {
+                padded
+            }
+        };
+
+        let part = match name {
+            "year" if has("repr:last_two") => "yy",
+            "year" => "yyyy",
+            "month" if has("repr:long") => "mmmm",
+            "month" if has("repr:short") => "mmm",
+            "month" => padded("mm", "m"),
+            "day" => padded("dd", "d"),
+            "weekday" if has("repr:short") => "ddd",
+            "weekday" => "dddd",
+            "hour" => padded("hh", "h"),
+            "minute" => padded("mm", "m"),
+            "second" => padded("ss", "s"),
+            "subsecond" => "000",
+            "period" => "AM/PM",
+            _ => return None,
+        };
+
+        num_format.push_str(part);
+    }
+
+    Some(num_format)
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+    use crate::chrono::RenderChronoOptions;
+    use ::chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+    fn num_format(options: &RenderChronoOptions, default: &str) -> String {
+        options
+            .string
+            .as_deref()
+            .and_then(strftime_to_num_format)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    fn excel_date(date: &NaiveDate) -> Result<ExcelDateTime, XlsxError> {
+        let year = u16::try_from(date.year())
+            .map_err(|_| XlsxError::DateTimeRangeError(date.to_string()))?;
+        ExcelDateTime::from_ymd(year, date.month() as u8, date.day() as u8)
+    }
+
+    /// Milliseconds of the time. Leap seconds are clamped to the last millisecond of the second.
+    fn milli(time: &NaiveTime) -> u16 {
+        (time.nanosecond() / 1_000_000).min(999) as u16
+    }
+
+    impl XlsxCellValue<NaiveDate> for NaiveDate {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            XlsxCell::date_time(excel_date(self), num_format(options, "yyyy-mm-dd"), || {
+                self.format_text(options)
+            })
+        }
+    }
+
+    impl XlsxCellValue<NaiveDateTime> for NaiveDateTime {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            let time = self.time();
+            let value = excel_date(&self.date()).and_then(|date| {
+                date.and_hms_milli(
+                    time.hour() as u16,
+                    time.minute() as u8,
+                    time.second() as u8,
+                    milli(&time),
+                )
+            });
+
+            XlsxCell::date_time(value, num_format(options, "yyyy-mm-dd hh:mm:ss"), || {
+                self.format_text(options)
+            })
+        }
+    }
+
+    impl XlsxCellValue<NaiveTime> for NaiveTime {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            let value = ExcelDateTime::from_hms_milli(
+                self.hour() as u16,
+                self.minute() as u8,
+                self.second() as u8,
+                milli(self),
+            );
+
+            XlsxCell::date_time(value, num_format(options, "hh:mm:ss"), || {
+                self.format_text(options)
+            })
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+    use crate::time::RenderTimeOptions;
+    use ::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    fn num_format(options: &RenderTimeOptions, default: &str) -> String {
+        options
+            .string
+            .as_deref()
+            .and_then(time_description_to_num_format)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    fn excel_date(date: Date) -> Result<ExcelDateTime, XlsxError> {
+        let year = u16::try_from(date.year())
+            .map_err(|_| XlsxError::DateTimeRangeError(date.to_string()))?;
+        ExcelDateTime::from_ymd(year, date.month().into(), date.day())
+    }
+
+    fn excel_date_time(date: Date, time: Time) -> Result<ExcelDateTime, XlsxError> {
+        excel_date(date).and_then(|date| {
+            date.and_hms_milli(
+                time.hour().into(),
+                time.minute(),
+                time.second(),
+                time.millisecond(),
+            )
+        })
+    }
+
+    impl XlsxCellValue<Date> for Date {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            XlsxCell::date_time(excel_date(*self), num_format(options, "yyyy-mm-dd"), || {
+                self.format_text(options)
+            })
+        }
+    }
+
+    impl XlsxCellValue<Time> for Time {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            let value = ExcelDateTime::from_hms_milli(
+                self.hour().into(),
+                self.minute(),
+                self.second(),
+                self.millisecond(),
+            );
+
+            XlsxCell::date_time(value, num_format(options, "hh:mm:ss"), || {
+                self.format_text(options)
+            })
+        }
+    }
+
+    impl XlsxCellValue<PrimitiveDateTime> for PrimitiveDateTime {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            XlsxCell::date_time(
+                excel_date_time(self.date(), self.time()),
+                num_format(options, "yyyy-mm-dd hh:mm:ss"),
+                || self.format_text(options),
+            )
+        }
+    }
+
+    /// Excel doesn't know time zones so the date and time in the offset of the value are written.
+    impl XlsxCellValue<OffsetDateTime> for OffsetDateTime {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            XlsxCell::date_time(
+                excel_date_time(self.date(), self.time()),
+                num_format(options, "yyyy-mm-dd hh:mm:ss"),
+                || self.format_text(options),
+            )
+        }
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impls {
+    use super::*;
+    use ::rust_decimal::Decimal;
+    use ::rust_decimal::prelude::ToPrimitive;
+
+    /// Excel stores numbers as 64 bit floats so decimals with more than 15 significant digits are rounded.
+    impl XlsxCellValue<Decimal> for Decimal {
+        fn xlsx_cell(&self, options: &Self::RenderOptions) -> XlsxCell {
+            match self.to_f64() {
+                Some(value) => XlsxCell::Number {
+                    value,
+                    num_format: number_num_format(&(*options).into(), false),
+                },
+                None => XlsxCell::Text(self.format_text(options)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl XlsxCellValue<::uuid::Uuid> for ::uuid::Uuid {
+    fn xlsx_cell(&self, _options: &Self::RenderOptions) -> XlsxCell {
+        XlsxCell::Text(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::prelude::*;
+    use std::collections::VecDeque;
+
+    #[derive(Clone)]
+    struct Invoice {
+        customer: String,
+        paid: bool,
+        amount: f64,
+    }
+
+    impl TableRow<usize> for Invoice {
+        type ClassesProvider = crate::DummyTableClassesProvider;
+
+        const COLUMN_COUNT: usize = 3;
+
+        fn render_row(_: RwSignal<Self>, _: usize, _: RwSignal<Vec<usize>>) -> impl IntoView {}
+
+        fn render_head_row<F>(
+            _: Signal<VecDeque<(usize, ColumnSort)>>,
+            _: F,
+            _: HeadDragHandler<usize>,
+            _: RwSignal<Vec<usize>>,
+        ) -> impl IntoView
+        where
+            F: Fn(TableHeadEvent<usize>) + Send + Clone + 'static,
+        {
+        }
+
+        // like `#[table(format(precision = 2usize))]` on `amount`
+        fn cell_renderer_for_column(
+            row: RwSignal<Self>,
+            column: usize,
+            class: String,
+        ) -> impl IntoView {
+            match column {
+                0 => view! {
+                    <DefaultTableCellRenderer<_, usize, String, ()>
+                        options=()
+                        value=Signal::derive(move || row.read().customer.clone())
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+                1 => view! {
+                    <DefaultTableCellRenderer<_, usize, bool, bool>
+                        options=()
+                        value=Signal::derive(move || row.read().paid)
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+                _ => view! {
+                    <DefaultTableCellRenderer<_, usize, f64, f64>
+                        options=NumberRenderOptions {
+                            precision: Some(2),
+                            ..Default::default()
+                        }
+                        value=Signal::derive(move || row.read().amount)
+                        class
+                        index=column
+                        row
+                    />
+                }
+                .into_any(),
+            }
+        }
+
+        fn col_name(column: usize) -> &'static str {
+            ["customer", "paid", "amount"][column]
+        }
+
+        fn columns() -> &'static [usize] {
+            &[0, 1, 2]
+        }
+    }
+
+    #[test]
+    fn cells_are_typed_with_the_format_options() {
+        let owner = Owner::new();
+        owner.set();
+
+        let invoice = Invoice {
+            customer: "ACME".to_string(),
+            paid: true,
+            amount: 12.5,
+        };
+
+        assert!(matches!(xlsx_cell_of(&invoice, 0), XlsxCell::Text(text) if text == "ACME"));
+        assert!(matches!(xlsx_cell_of(&invoice, 1), XlsxCell::Boolean(true)));
+        assert!(matches!(
+            xlsx_cell_of(&invoice, 2),
+            XlsxCell::Number { value, num_format: Some(f) } if value == 12.5 && f == "0.00"
+        ));
+
+        let mut row_cells = XlsxCellGetter::<Invoice, usize>::from_cell_renderers().cells(&[1, 2]);
+        row_cells(&invoice);
+        let cells = row_cells(&Invoice {
+            customer: "Initech".to_string(),
+            paid: false,
+            amount: 3.0,
+        });
+        assert!(matches!(cells[0], XlsxCell::Boolean(false)));
+        assert!(matches!(cells[1], XlsxCell::Number { value, .. } if value == 3.0));
+    }
+
+    #[test]
+    fn quotes_in_literals_are_doubled() {
+        assert_eq!(quoted_literal("\"kg\""), "\"\"\"kg\"\"\"");
+        assert_eq!(quoted_literal(""), "");
+    }
+
+    #[test]
+    fn number_format_follows_precision() {
+        let cell = 2.71_f64.xlsx_cell(&NumberRenderOptions {
+            precision: Some(2),
+            ..Default::default()
+        });
+        assert!(matches!(
+            cell,
+            XlsxCell::Number { value, num_format: Some(f) } if value == 2.71 && f == "0.00"
+        ));
+
+        let cell = 42_u8.xlsx_cell(&NumberRenderOptions {
+            precision: Some(0),
+            ..Default::default()
+        });
+        assert!(matches!(cell, XlsxCell::Number { num_format: Some(f), .. } if f == "0"));
+
+        let cell = 42_i64.xlsx_cell(&NumberRenderOptions::default());
+        assert!(matches!(
+            cell,
+            XlsxCell::Number {
+                num_format: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn decimals_are_kept_without_precision() {
+        let options = NumberRenderOptions {
+            thousands_separator: Some(true),
+            ..Default::default()
+        };
+
+        let cell = 1234.5678_f64.xlsx_cell(&options);
+        assert!(matches!(
+            cell,
+            XlsxCell::Number { num_format: Some(f), .. } if f == "#,##0.###############"
+        ));
+
+        let cell = 0.125_f64.xlsx_cell(&NumberRenderOptions {
+            percent: Some(true),
+            ..Default::default()
+        });
+        assert!(matches!(
+            cell,
+            XlsxCell::Number { num_format: Some(f), .. } if f == "0.###############%"
+        ));
+
+        // integers have no decimals, so they aren't shown with a trailing decimal point
+        let cell = 1234_u32.xlsx_cell(&options);
+        assert!(matches!(cell, XlsxCell::Number { num_format: Some(f), .. } if f == "#,##0"));
+
+        // and aren't padded with zeros like in the table
+        let cell = 5_i32.xlsx_cell(&NumberRenderOptions {
+            precision: Some(2),
+            ..Default::default()
+        });
+        assert!(matches!(cell, XlsxCell::Number { num_format: Some(f), .. } if f == "0"));
+    }
+
+    #[test]
+    fn number_format_with_currency_and_unit() {
+        let cell = 9.5_f64.xlsx_cell(&NumberRenderOptions {
+            currency: Some("EUR"),
+            locale: Some(NumberLocale::DE),
+            sign_display: Some(SignDisplay::ExceptZero),
+            ..Default::default()
+        });
+        assert!(matches!(
+            cell,
+            XlsxCell::Number { num_format: Some(f), .. }
+                if f == "+0.00\"\u{a0}€\";-0.00\"\u{a0}€\";0.00\"\u{a0}€\""
+        ));
+
+        let cell = 70_u32.xlsx_cell(&NumberRenderOptions {
+            unit: Some("kg"),
+            thousands_separator: Some(true),
+            ..Default::default()
+        });
+        assert!(matches!(
+            cell,
+            XlsxCell::Number { num_format: Some(f), .. } if f == "#,##0\"\u{a0}kg\""
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn strftime_is_converted() {
+        assert_eq!(
+            strftime_to_num_format("%d.%m.%Y %H:%M").as_deref(),
+            Some("dd.mm.yyyy hh:mm")
+        );
+        assert_eq!(
+            strftime_to_num_format("%-d %B %Y, at %I%p").as_deref(),
+            Some("d mmmm yyyy, \\a\\t hhAM/PM")
+        );
+        assert_eq!(strftime_to_num_format("%Z"), None);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_description_is_converted() {
+        assert_eq!(
+            time_description_to_num_format("[day]/[month repr:short]/[year repr:last_two]")
+                .as_deref(),
+            Some("dd/mmm/yy")
+        );
+        assert_eq!(
+            time_description_to_num_format("[hour padding:none]:[minute]").as_deref(),
+            Some("h:mm")
+        );
+        assert_eq!(time_description_to_num_format("[offset_hour]"), None);
+    }
+}